@@ -0,0 +1,47 @@
+//! Times `merkleize` over a 2^20-chunk tree.
+//!
+//! Run once without and once with the `rayon` feature to compare the sequential and parallel
+//! layer hashing; both must print the same root:
+//!
+//! ```text
+//! cargo bench --bench merkleize
+//! cargo bench --bench merkleize --features rayon
+//! ```
+
+use r_ssz::{BYTES_PER_CHUNK, merkleization::merkleize};
+use std::time::{Duration, Instant};
+
+fn main() {
+    const CHUNKS: u32 = 1 << 20;
+    const ITERATIONS: u32 = 10;
+
+    let chunks: Vec<[u8; BYTES_PER_CHUNK]> = (0..CHUNKS)
+        .map(|i| {
+            let mut chunk = [0u8; BYTES_PER_CHUNK];
+            chunk[..4].copy_from_slice(&i.to_le_bytes());
+            chunk
+        })
+        .collect();
+
+    // Warm up, and make sure the root is printed so the work isn't optimized away.
+    let root = merkleize(&chunks, None).unwrap();
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let again = merkleize(&chunks, None).unwrap();
+        total += start.elapsed();
+        assert_eq!(again, root);
+    }
+
+    let mode = if cfg!(feature = "rayon") {
+        "parallel"
+    } else {
+        "sequential"
+    };
+    println!("root: {root}");
+    println!(
+        "merkleize 2^20 chunks ({mode}): {:.2} ms/iter",
+        total.as_secs_f64() * 1000.0 / ITERATIONS as f64
+    );
+}
@@ -0,0 +1,105 @@
+//! Counts heap allocations made while serializing `MyStableContainer`.
+//!
+//! Compares the single-pass `serialize_fields` assembler against the previous approach of
+//! serializing every active field into its own buffer and cloning it into fixed/variable parts.
+//!
+//! Run with `cargo bench --bench stable_container_alloc`.
+
+use r_ssz::{BitVector, MyStableContainer, SimpleSerialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// The per-field buffer approach `MyStableContainer::serialize` used before `serialize_fields`.
+fn serialize_per_field_buffers(value: &MyStableContainer, buffer: &mut Vec<u8>) {
+    let mut flags = vec![value.a.is_some(), value.b.is_some(), value.c.is_some()];
+    flags.resize(4, false);
+    BitVector::<4>::from_bools(&flags)
+        .unwrap()
+        .serialize(buffer)
+        .unwrap();
+
+    let mut parts: Vec<Vec<u8>> = Vec::new();
+    if let Some(a) = value.a {
+        let mut buf = Vec::new();
+        a.serialize(&mut buf).unwrap();
+        parts.push(buf);
+    }
+    if let Some(b) = value.b {
+        let mut buf = Vec::new();
+        b.serialize(&mut buf).unwrap();
+        parts.push(buf);
+    }
+    if let Some(c) = value.c {
+        let mut buf = Vec::new();
+        c.serialize(&mut buf).unwrap();
+        parts.push(buf);
+    }
+    let fixed_parts: Vec<Vec<u8>> = parts.to_vec();
+    for part in fixed_parts {
+        buffer.extend_from_slice(&part);
+    }
+}
+
+fn count_allocations(mut f: impl FnMut()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn main() {
+    const ITERATIONS: usize = 10_000;
+    let value = MyStableContainer {
+        a: Some(42),
+        b: Some(true),
+        c: Some(1000),
+    };
+
+    let mut expected = Vec::new();
+    serialize_per_field_buffers(&value, &mut expected);
+    let mut actual = Vec::new();
+    value.serialize(&mut actual).unwrap();
+    assert_eq!(
+        actual, expected,
+        "both approaches must produce identical bytes"
+    );
+
+    let before = count_allocations(|| {
+        for _ in 0..ITERATIONS {
+            let mut buffer = Vec::new();
+            serialize_per_field_buffers(&value, &mut buffer);
+        }
+    });
+    let after = count_allocations(|| {
+        for _ in 0..ITERATIONS {
+            let mut buffer = Vec::new();
+            value.serialize(&mut buffer).unwrap();
+        }
+    });
+
+    println!(
+        "per-field buffers: {:.1} allocations/serialize",
+        before as f64 / ITERATIONS as f64
+    );
+    println!(
+        "serialize_fields:  {:.1} allocations/serialize",
+        after as f64 / ITERATIONS as f64
+    );
+}
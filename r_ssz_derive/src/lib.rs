@@ -0,0 +1,208 @@
+//! Derive macros for the SSZ container traits of `r_ssz`.
+//!
+//! Each derive treats a struct with named fields as an SSZ container whose fields are encoded
+//! in declaration order. The generated code refers to the traits through `::r_ssz`, so the
+//! macros are meant to be used through the re-exports in `r_ssz`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, Type, parse_macro_input, parse_quote};
+
+/// Named fields of a derived container, in declaration order.
+struct Container {
+    input: DeriveInput,
+    idents: Vec<Ident>,
+    types: Vec<Type>,
+}
+
+impl Container {
+    fn parse(input: DeriveInput) -> syn::Result<Self> {
+        let Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "SSZ containers can only be derived for structs",
+            ));
+        };
+        let Fields::Named(fields) = &data.fields else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "SSZ containers need named fields",
+            ));
+        };
+        if fields.named.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "SSZ containers need at least one field",
+            ));
+        }
+
+        let idents = fields
+            .named
+            .iter()
+            .map(|field| field.ident.clone().expect("named field"))
+            .collect();
+        let types = fields.named.iter().map(|field| field.ty.clone()).collect();
+        Ok(Self {
+            input,
+            idents,
+            types,
+        })
+    }
+
+    /// Writes `impl #trait_path for #name { #body }`, bounding every field type by `bounds`.
+    fn impl_with_bounds(
+        &self,
+        trait_path: TokenStream2,
+        bounds: TokenStream2,
+        body: TokenStream2,
+    ) -> TokenStream2 {
+        let name = &self.input.ident;
+        let mut generics = self.input.generics.clone();
+        if !generics.params.is_empty() {
+            let where_clause = generics.make_where_clause();
+            for ty in &self.types {
+                where_clause.predicates.push(parse_quote!(#ty: #bounds));
+            }
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        quote! {
+            impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+                #body
+            }
+        }
+    }
+}
+
+/// Parses the derive input and expands it with `expand`, reporting errors at the struct.
+fn derive_with(input: TokenStream, expand: fn(&Container) -> TokenStream2) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match Container::parse(input) {
+        Ok(container) => expand(&container).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derives `SszTypeInfo`: the container is fixed-size when all of its fields are, and its
+/// fixed size is then the sum of theirs.
+#[proc_macro_derive(SszTypeInfo)]
+pub fn derive_ssz_type_info(input: TokenStream) -> TokenStream {
+    derive_with(input, |container| {
+        let types = &container.types;
+        container.impl_with_bounds(
+            quote!(::r_ssz::SszTypeInfo),
+            quote!(::r_ssz::SszTypeInfo),
+            quote! {
+                const IS_FIXED: bool = true #(&& <#types as ::r_ssz::SszTypeInfo>::IS_FIXED)*;
+
+                fn fixed_size() -> ::core::option::Option<usize> {
+                    let mut size = 0;
+                    #(size += <#types as ::r_ssz::SszTypeInfo>::fixed_size()?;)*
+                    ::core::option::Option::Some(size)
+                }
+            },
+        )
+    })
+}
+
+/// Derives `SimpleSerialize`: fixed-size fields are written inline and variable-size fields as
+/// offsets into the variable section that follows, in declaration order, with
+/// `r_ssz::container::serialize_fields`. Whether a field is fixed-size comes from its type's
+/// `SszTypeInfo::IS_FIXED`.
+#[proc_macro_derive(SimpleSerialize)]
+pub fn derive_simple_serialize(input: TokenStream) -> TokenStream {
+    derive_with(input, |container| {
+        let idents = &container.idents;
+        let types = &container.types;
+        container.impl_with_bounds(
+            quote!(::r_ssz::SimpleSerialize),
+            quote!(::r_ssz::SimpleSerialize + ::r_ssz::SszTypeInfo),
+            quote! {
+                fn serialize(
+                    &self,
+                    buffer: &mut ::r_ssz::__private::Vec<u8>,
+                ) -> ::core::result::Result<usize, ::r_ssz::SSZError> {
+                    ::r_ssz::container::serialize_fields(
+                        [#((
+                            &self.#idents as &dyn ::r_ssz::SimpleSerialize,
+                            <#types as ::r_ssz::SszTypeInfo>::IS_FIXED,
+                        )),*],
+                        buffer,
+                    )
+                }
+
+                fn ssz_bytes_len(&self) -> ::core::result::Result<usize, ::r_ssz::SSZError> {
+                    let mut len = 0;
+                    #(
+                        if !<#types as ::r_ssz::SszTypeInfo>::IS_FIXED {
+                            len += ::r_ssz::BYTES_PER_LENGTH_OFFSET;
+                        }
+                        len += ::r_ssz::SimpleSerialize::ssz_bytes_len(&self.#idents)?;
+                    )*
+                    ::core::result::Result::Ok(len)
+                }
+
+                ::r_ssz::__ssz_container_writer!(#(#idents: #types),*);
+            },
+        )
+    })
+}
+
+/// Derives `SimpleDeserialize`: the input is split into field encodings with
+/// `r_ssz::container::split_fields`, which reads the fixed section and the offsets of
+/// variable-size fields, rejects decreasing offsets and trailing bytes, and each field is then
+/// decoded from its slice.
+#[proc_macro_derive(SimpleDeserialize)]
+pub fn derive_simple_deserialize(input: TokenStream) -> TokenStream {
+    derive_with(input, |container| {
+        let idents = &container.idents;
+        let types = &container.types;
+        let indices = 0..idents.len();
+        container.impl_with_bounds(
+            quote!(::r_ssz::SimpleDeserialize),
+            quote!(::r_ssz::SimpleDeserialize + ::r_ssz::SszTypeInfo),
+            quote! {
+                fn deserialize(data: &[u8]) -> ::core::result::Result<Self, ::r_ssz::SSZError> {
+                    let fields = ::r_ssz::container::split_fields(
+                        data,
+                        &[#(<#types as ::r_ssz::SszTypeInfo>::fixed_size()),*],
+                    )?;
+                    ::core::result::Result::Ok(Self {
+                        #(#idents: <#types as ::r_ssz::SimpleDeserialize>::deserialize(
+                            fields[#indices],
+                        )?),*
+                    })
+                }
+            },
+        )
+    })
+}
+
+/// Derives `Merkleize`: the root merkleizes the fields' `hash_tree_root`s in declaration order
+/// with `r_ssz::merkleization::merkleize_fields`, padded to the next power of two, and
+/// `chunk_count` is the number of fields.
+#[proc_macro_derive(Merkleize)]
+pub fn derive_merkleize(input: TokenStream) -> TokenStream {
+    derive_with(input, |container| {
+        let idents = &container.idents;
+        let field_count = idents.len();
+        container.impl_with_bounds(
+            quote!(::r_ssz::Merkleize),
+            quote!(::r_ssz::Merkleize),
+            quote! {
+                fn hash_tree_root(
+                    &self,
+                ) -> ::core::result::Result<::r_ssz::__private::B256, ::r_ssz::SSZError> {
+                    ::r_ssz::merkleization::merkleize_fields(::r_ssz::__private::Vec::from([
+                        #(::r_ssz::Merkleize::hash_tree_root(&self.#idents)?),*
+                    ]))
+                }
+
+                fn chunk_count() -> usize {
+                    #field_count
+                }
+            },
+        )
+    })
+}
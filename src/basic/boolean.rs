@@ -16,6 +16,10 @@ impl SimpleSerialize for bool {
         buffer.push(if *self { 1 } else { 0 });
         Ok(buffer.len())
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(1)
+    }
 }
 
 impl SimpleDeserialize for bool {
@@ -37,9 +41,7 @@ impl SimpleDeserialize for bool {
 
 impl SszTypeInfo for bool {
     /// Indicates that the boolean type is fixed-size.
-    fn is_fixed_size() -> bool {
-        true
-    }
+    const IS_FIXED: bool = true;
 
     /// Returns the fixed size of a boolean value in bytes.
     fn fixed_size() -> Option<usize> {
@@ -4,32 +4,37 @@ use crate::Merkleize;
 use crate::SimpleDeserialize;
 use crate::SszTypeInfo;
 use crate::constants::BYTES;
+use crate::merkleization::pad_to_chunk;
 use crate::{SSZError, SimpleSerialize};
 use alloc::vec::Vec;
 use alloy_primitives::{B256, U256};
 use core::{option::Option, result::Result};
 
 macro_rules! impl_uint {
-    ($type:ty, $bytes:expr) => {
+    ($type:ty) => {
         impl SimpleSerialize for $type {
             /// Implements serialization for unsigned integers.
             fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-                let bytes: [u8; $bytes] = self.to_le_bytes();
+                let bytes: [u8; size_of::<$type>()] = self.to_le_bytes();
                 buffer.extend_from_slice(&bytes);
                 Ok(bytes.len())
             }
+
+            fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+                Ok(size_of::<$type>())
+            }
         }
 
         impl SimpleDeserialize for $type {
             /// Implements the deserialization trait for unsigned integers.
             fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-                if data.len() != $bytes {
+                if data.len() != size_of::<$type>() {
                     return Err(SSZError::InvalidLength {
-                        expected: $bytes,
+                        expected: size_of::<$type>(),
                         got: data.len(),
                     });
                 }
-                let mut bytes = [0u8; $bytes];
+                let mut bytes = [0u8; size_of::<$type>()];
                 bytes.copy_from_slice(data);
                 Ok(Self::from_le_bytes(bytes))
             }
@@ -37,11 +42,11 @@ macro_rules! impl_uint {
     };
 }
 
-impl_uint!(u8, 1);
-impl_uint!(u16, 2);
-impl_uint!(u32, 4);
-impl_uint!(u64, 8);
-impl_uint!(u128, 16);
+impl_uint!(u8);
+impl_uint!(u16);
+impl_uint!(u32);
+impl_uint!(u64);
+impl_uint!(u128);
 
 impl SimpleSerialize for U256 {
     /// Implements serialization for U256.
@@ -50,6 +55,10 @@ impl SimpleSerialize for U256 {
         buffer.extend_from_slice(&bytes);
         Ok(bytes.len())
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(BYTES)
+    }
 }
 
 impl SimpleDeserialize for U256 {
@@ -68,16 +77,14 @@ impl SimpleDeserialize for U256 {
 }
 
 macro_rules! impl_uint_typeinfo {
-    ($type:ty, $bytes:expr) => {
+    ($type:ty) => {
         impl SszTypeInfo for $type {
-            /// Returns true if the type is fixed-size.
-            fn is_fixed_size() -> bool {
-                true
-            }
+            /// Uints are fixed-size.
+            const IS_FIXED: bool = true;
 
             /// Returns the fixed size in bytes.
             fn fixed_size() -> Option<usize> {
-                Some($bytes)
+                Some(size_of::<$type>())
             }
 
             ///Returns true since it is basic type.
@@ -87,17 +94,15 @@ macro_rules! impl_uint_typeinfo {
         }
     };
 }
-impl_uint_typeinfo!(u8, 1);
-impl_uint_typeinfo!(u16, 2);
-impl_uint_typeinfo!(u32, 4);
-impl_uint_typeinfo!(u64, 8);
-impl_uint_typeinfo!(u128, 16);
+impl_uint_typeinfo!(u8);
+impl_uint_typeinfo!(u16);
+impl_uint_typeinfo!(u32);
+impl_uint_typeinfo!(u64);
+impl_uint_typeinfo!(u128);
 
 impl SszTypeInfo for U256 {
-    /// Returns true if the type is fixed-size.
-    fn is_fixed_size() -> bool {
-        true
-    }
+    /// Uints are fixed-size.
+    const IS_FIXED: bool = true;
 
     /// Returns the fixed size in bytes.
     fn fixed_size() -> Option<usize> {
@@ -110,24 +115,21 @@ impl SszTypeInfo for U256 {
     }
 }
 macro_rules! impl_uint_merkleize {
-    ($type:ty, $bytes:expr) => {
+    ($type:ty) => {
         impl Merkleize for $type {
             /// Returns `hash_tree_root` for uint.
             fn hash_tree_root(&self) -> Result<B256, SSZError> {
-                let bytes = self.to_le_bytes();
-                let mut buf = [0u8; 32];
-                buf[..$bytes].copy_from_slice(&bytes);
-                Ok(B256::from(buf))
+                Ok(B256::from(pad_to_chunk(&self.to_le_bytes())?))
             }
         }
     };
 }
 
-impl_uint_merkleize!(u8, 1);
-impl_uint_merkleize!(u16, 2);
-impl_uint_merkleize!(u32, 4);
-impl_uint_merkleize!(u64, 8);
-impl_uint_merkleize!(u128, 16);
+impl_uint_merkleize!(u8);
+impl_uint_merkleize!(u16);
+impl_uint_merkleize!(u32);
+impl_uint_merkleize!(u64);
+impl_uint_merkleize!(u128);
 
 impl Merkleize for U256 {
     /// Returns `hash_tree_root` for u256.
@@ -146,6 +148,17 @@ mod tests {
     use alloc::vec::Vec;
     use alloy_primitives::hex;
 
+    #[test]
+    fn test_uint_fixed_size_matches_size_of() {
+        assert_eq!(u8::fixed_size(), Some(size_of::<u8>()));
+        assert_eq!(u16::fixed_size(), Some(size_of::<u16>()));
+        assert_eq!(u32::fixed_size(), Some(size_of::<u32>()));
+        assert_eq!(u64::fixed_size(), Some(size_of::<u64>()));
+        assert_eq!(u128::fixed_size(), Some(size_of::<u128>()));
+        assert_eq!(U256::fixed_size(), Some(size_of::<U256>()));
+        assert_eq!(U256::fixed_size(), Some(BYTES));
+    }
+
     #[test]
     fn test_uint_serialize() {
         let mut buffer = vec![];
@@ -240,4 +253,26 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_u256_hash_tree_root_is_little_endian() {
+        let root = U256::from(1).hash_tree_root().unwrap();
+        assert_eq!(
+            root,
+            B256::from(hex!(
+                "0100000000000000000000000000000000000000000000000000000000000000"
+            ))
+        );
+
+        // Same right-padded little-endian layout as the narrower uints.
+        assert_eq!(root, 1u8.hash_tree_root().unwrap());
+        assert_eq!(root, 1u64.hash_tree_root().unwrap());
+        assert_eq!(root, 1u128.hash_tree_root().unwrap());
+
+        let value = 0x0102_0304_0506_0708u64;
+        assert_eq!(
+            U256::from(value).hash_tree_root().unwrap(),
+            value.hash_tree_root().unwrap()
+        );
+    }
 }
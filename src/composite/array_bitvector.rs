@@ -0,0 +1,370 @@
+//! Serialization , deserialization and merkleization for a heap-free BitVector.
+
+use crate::{
+    Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
+    merkleization::{merkleize, pack_bits},
+};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+/// SSZ `Bitvector[N]` stored inline as its `B`-byte encoding, without a heap allocation.
+///
+/// Stable Rust can't size an array by `N.div_ceil(8)`, so the byte length is a second
+/// parameter and must equal `N.div_ceil(8)`; any other value fails to compile once the type is
+/// constructed or deserialized. Encodes and merkleizes exactly like [`BitVector<N>`].
+///
+/// ```compile_fail
+/// use r_ssz::ArrayBitVector;
+/// let _ = ArrayBitVector::<11, 1>::new();
+/// ```
+///
+/// [`BitVector<N>`]: crate::BitVector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayBitVector<const N: usize, const B: usize> {
+    // bits past `N` in the last byte are always zero
+    bytes: [u8; B],
+}
+
+impl<const N: usize, const B: usize> Default for ArrayBitVector<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const B: usize> ArrayBitVector<N, B> {
+    pub fn new() -> Self {
+        const {
+            assert!(
+                B == N.div_ceil(8),
+                "ArrayBitVector<N, B> needs B == N.div_ceil(8)"
+            )
+        };
+        Self { bytes: [0u8; B] }
+    }
+
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < N).then(|| (self.bytes[index / 8] >> (index % 8)) & 1 == 1)
+    }
+
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the SSZ encoding of the bit vector.
+    pub fn as_bytes(&self) -> &[u8; B] {
+        &self.bytes
+    }
+
+    /// Returns the indices of the set bits, in increasing order.
+    pub fn set_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..N).filter(|&index| self.get(index) == Some(true))
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) -> Result<(), SSZError> {
+        if index >= N {
+            return Err(SSZError::InvalidLength {
+                expected: N,
+                got: index,
+            });
+        }
+        if value {
+            self.bytes[index / 8] |= 1 << (index % 8);
+        } else {
+            self.bytes[index / 8] &= !(1 << (index % 8));
+        }
+        Ok(())
+    }
+
+    /// Builds a bit vector from its first `bools.len()` bits, leaving the rest unset.
+    ///
+    /// Fails if there are more than `N` bits.
+    pub fn from_bools(bools: &[bool]) -> Result<Self, SSZError> {
+        if bools.len() > N {
+            return Err(SSZError::InvalidLength {
+                expected: N,
+                got: bools.len(),
+            });
+        }
+        let mut bv = Self::new();
+        for (i, &bit) in bools.iter().enumerate() {
+            if bit {
+                bv.bytes[i / 8] |= 1 << (i % 8);
+            }
+        }
+        Ok(bv)
+    }
+
+    /// Returns the number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.bytes
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+
+    /// Clears the padding bits past `N` in the last byte.
+    fn clear_padding(&mut self) {
+        if !N.is_multiple_of(8) {
+            self.bytes[B - 1] &= (1 << (N % 8)) - 1;
+        }
+    }
+
+    /// Deserializes a bit vector from its SSZ encoding as a hex string.
+    pub fn from_hex(s: &str) -> Result<Self, SSZError> {
+        crate::hex_util::from_hex(s)
+    }
+
+    /// Serializes the bit vector and encodes it as a `0x`-prefixed hex string.
+    pub fn to_hex(&self) -> Result<String, SSZError> {
+        crate::hex_util::to_hex(self)
+    }
+}
+
+impl<const N: usize, const B: usize> SszTypeInfo for ArrayBitVector<N, B> {
+    /// Indicates that the bit vector is fixed-size.
+    const IS_FIXED: bool = true;
+
+    /// Returns the fixed size of the bit vector in bytes.
+    fn fixed_size() -> Option<usize> {
+        Some(B)
+    }
+}
+
+impl<const N: usize, const B: usize> SimpleSerialize for ArrayBitVector<N, B> {
+    /// Serializes a bit vector.
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        buffer.extend_from_slice(&self.bytes);
+        Ok(B)
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(B)
+    }
+}
+
+impl<const N: usize, const B: usize> SimpleDeserialize for ArrayBitVector<N, B> {
+    /// Deserializes a bit vector, ignoring padding bits past `N` as `BitVector` does.
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        let mut bv = Self::new();
+        bv.bytes = data.try_into().map_err(|_| SSZError::TypeMismatch {
+            context: format!("ArrayBitVector<{N}> needs {B} bytes, got {}", data.len()),
+        })?;
+        bv.clear_padding();
+        Ok(bv)
+    }
+}
+
+/// Implements a bitwise operator for `ArrayBitVector<N, B>`, by value and by reference.
+macro_rules! impl_bitwise_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl<const N: usize, const B: usize> $trait for &ArrayBitVector<N, B> {
+            type Output = ArrayBitVector<N, B>;
+
+            fn $method(self, rhs: Self) -> ArrayBitVector<N, B> {
+                ArrayBitVector {
+                    bytes: core::array::from_fn(|i| self.bytes[i] $op rhs.bytes[i]),
+                }
+            }
+        }
+
+        impl<const N: usize, const B: usize> $trait for ArrayBitVector<N, B> {
+            type Output = ArrayBitVector<N, B>;
+
+            fn $method(self, rhs: Self) -> ArrayBitVector<N, B> {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_bitwise_op!(BitAnd, bitand, &);
+impl_bitwise_op!(BitOr, bitor, |);
+impl_bitwise_op!(BitXor, bitxor, ^);
+
+/// Flips each of the `N` bits; the padding bits of the encoding stay zero.
+impl<const N: usize, const B: usize> Not for &ArrayBitVector<N, B> {
+    type Output = ArrayBitVector<N, B>;
+
+    fn not(self) -> ArrayBitVector<N, B> {
+        let mut bv = ArrayBitVector {
+            bytes: self.bytes.map(|byte| !byte),
+        };
+        bv.clear_padding();
+        bv
+    }
+}
+
+impl<const N: usize, const B: usize> Not for ArrayBitVector<N, B> {
+    type Output = ArrayBitVector<N, B>;
+
+    fn not(self) -> ArrayBitVector<N, B> {
+        !&self
+    }
+}
+
+/// Implements `hash_tree_root` for ArrayBitVector
+impl<const N: usize, const B: usize> Merkleize for ArrayBitVector<N, B> {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        let chunks = pack_bits(&self.bytes);
+        merkleize(&chunks, Some(Self::chunk_count()))
+    }
+
+    fn chunk_count() -> usize {
+        N.div_ceil(256)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BitVector;
+    use alloc::vec;
+
+    fn assert_matches_bitvector<const N: usize, const B: usize>(bools: &[bool]) {
+        let array = ArrayBitVector::<N, B>::from_bools(bools).unwrap();
+        let heap = BitVector::<N>::from_bools(bools).unwrap();
+
+        let mut array_bytes = vec![];
+        array.serialize(&mut array_bytes).unwrap();
+        let mut heap_bytes = vec![];
+        heap.serialize(&mut heap_bytes).unwrap();
+        assert_eq!(array_bytes, heap_bytes);
+        assert_eq!(
+            array.hash_tree_root().unwrap(),
+            heap.hash_tree_root().unwrap()
+        );
+        assert_eq!(ArrayBitVector::<N, B>::deserialize(&array_bytes), Ok(array));
+        assert_eq!(
+            array.set_indices().collect::<Vec<_>>(),
+            heap.set_indices().collect::<Vec<_>>()
+        );
+        assert_eq!(array.count_ones(), heap.count_ones());
+        for index in 0..=N {
+            assert_eq!(array.get(index), heap.get(index));
+        }
+        assert_eq!(array.to_hex(), heap.to_hex());
+    }
+
+    fn encode(value: &impl SimpleSerialize) -> Vec<u8> {
+        let mut buffer = vec![];
+        value.serialize(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_array_bitvector_behaves_like_bitvector() {
+        let patterns: [&[bool]; 4] = [
+            &[],
+            &[true, false, true],
+            &[
+                false, true, true, false, true, false, false, true, true, false, true,
+            ],
+            &[true; 11],
+        ];
+        for bools in patterns {
+            assert_matches_bitvector::<11, 2>(bools);
+        }
+
+        for lhs in patterns {
+            for rhs in patterns {
+                let (a, b) = (
+                    ArrayBitVector::<11, 2>::from_bools(lhs).unwrap(),
+                    ArrayBitVector::<11, 2>::from_bools(rhs).unwrap(),
+                );
+                let (x, y) = (
+                    BitVector::<11>::from_bools(lhs).unwrap(),
+                    BitVector::<11>::from_bools(rhs).unwrap(),
+                );
+                assert_eq!(encode(&(a & b)), encode(&(&x & &y)));
+                assert_eq!(encode(&(a | b)), encode(&(&x | &y)));
+                assert_eq!(encode(&(a ^ b)), encode(&(&x ^ &y)));
+                assert_eq!(encode(&!a), encode(&!&x));
+            }
+        }
+
+        // Both reject the same out-of-range writes and over-length input.
+        let mut array = ArrayBitVector::<11, 2>::new();
+        let mut heap = BitVector::<11>::new();
+        assert_eq!(array.set(11, true), heap.set(11, true));
+        assert_eq!(
+            ArrayBitVector::<11, 2>::from_bools(&[true; 12]).map(|bv| encode(&bv)),
+            BitVector::<11>::from_bools(&[true; 12]).map(|bv| encode(&bv))
+        );
+    }
+
+    #[test]
+    fn test_array_bitvector_matches_bitvector() {
+        assert_matches_bitvector::<8, 1>(&[false, false, false, true, true, false, false, true]);
+        assert_matches_bitvector::<11, 2>(&[
+            true, false, false, true, false, false, false, false, true, false, true,
+        ]);
+    }
+
+    #[test]
+    fn test_array_bitvector_from_bools() {
+        let bv = ArrayBitVector::<11, 2>::from_bools(&[true, false, true]).unwrap();
+        assert_eq!(bv.as_bytes(), &[0b101, 0]);
+        assert_eq!(bv.count_ones(), 2);
+        assert_eq!(
+            ArrayBitVector::<11, 2>::from_bools(&[]),
+            Ok(ArrayBitVector::new())
+        );
+        assert_eq!(ArrayBitVector::<11, 2>::new().count_ones(), 0);
+        assert_eq!(
+            ArrayBitVector::<11, 2>::from_bools(&[false; 12]),
+            Err(SSZError::InvalidLength {
+                expected: 11,
+                got: 12
+            })
+        );
+
+        // Short input zero-fills the same way as `BitVector::from_bools`.
+        assert_matches_bitvector::<11, 2>(&[true, true, false, true]);
+    }
+
+    #[test]
+    fn test_array_bitvector_bitwise_ops() {
+        let a = ArrayBitVector::<12, 2>::deserialize(&[0b1010_1100, 0b0000_1001]).unwrap();
+        let b = ArrayBitVector::<12, 2>::deserialize(&[0b0110_0101, 0b0000_0011]).unwrap();
+
+        assert_eq!((a & b).as_bytes(), &[0b0010_0100, 0b0000_0001]);
+        assert_eq!((a | b).as_bytes(), &[0b1110_1101, 0b0000_1011]);
+        assert_eq!((a ^ b).as_bytes(), &[0b1100_1001, 0b0000_1010]);
+        // Only the 12 bits are flipped; the top four padding bits stay zero.
+        assert_eq!((!&a).as_bytes(), &[0b0101_0011, 0b0000_0110]);
+
+        assert_eq!(!!ArrayBitVector::<12, 2>::new(), ArrayBitVector::new());
+        assert_eq!((!a & a).count_ones(), 0);
+    }
+
+    #[test]
+    fn test_array_bitvector_set_and_get() {
+        let mut bv = ArrayBitVector::<11, 2>::new();
+        bv.set(10, true).unwrap();
+        bv.set(3, true).unwrap();
+        bv.set(3, false).unwrap();
+        assert_eq!(bv.get(10), Some(true));
+        assert_eq!(bv.get(3), Some(false));
+        assert_eq!(bv.get(11), None);
+        assert_eq!(bv.as_bytes(), &[0, 0b100]);
+        assert!(bv.set(11, true).is_err());
+    }
+
+    #[test]
+    fn test_array_bitvector_deserialize() {
+        // Padding bits past N are dropped, as `BitVector` does.
+        let bv = ArrayBitVector::<11, 2>::deserialize(&[0xff, 0xff]).unwrap();
+        assert_eq!(bv.as_bytes(), &[0xff, 0b111]);
+        assert!(matches!(
+            ArrayBitVector::<11, 2>::deserialize(&[0xff]),
+            Err(SSZError::TypeMismatch { .. })
+        ));
+    }
+}
@@ -1,9 +1,10 @@
 //! Serialization , deserialization and merkleization for bitlist.
 
 use crate::{
-    Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
-    merkleization::{merkleize, mix_in_length, pack},
+    Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
+    merkleization::{get_depth, merkleize, mix_in_length, pack},
 };
+use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 use alloy_primitives::B256;
@@ -43,6 +44,10 @@ impl<const N: usize> BitList<N> {
         Ok(Self { bits })
     }
 
+    pub fn from_bools(bools: &[bool]) -> Result<Self, SSZError> {
+        Self::from_vec(bools.to_vec())
+    }
+
     pub fn push(&mut self, bit: bool) -> Result<(), SSZError> {
         if self.bits.len() >= N {
             return Err(SSZError::InvalidLength {
@@ -54,6 +59,25 @@ impl<const N: usize> BitList<N> {
         Ok(())
     }
 
+    /// Appends `bit_count` bits unpacked little-endian from `bytes`, as in the SSZ bitfield layout.
+    pub fn extend_from_bytes(&mut self, bytes: &[u8], bit_count: usize) -> Result<(), SSZError> {
+        if bytes.len() < bit_count.div_ceil(8) {
+            return Err(SSZError::InvalidByteLength {
+                expected: bit_count.div_ceil(8),
+                got: bytes.len(),
+            });
+        }
+        if self.bits.len() + bit_count > N {
+            return Err(SSZError::InvalidLength {
+                expected: N,
+                got: self.bits.len() + bit_count,
+            });
+        }
+        self.bits
+            .extend((0..bit_count).map(|i| (bytes[i / 8] >> (i % 8)) & 1 == 1));
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
         self.bits.len()
     }
@@ -61,13 +85,72 @@ impl<const N: usize> BitList<N> {
     pub fn is_empty(&self) -> bool {
         self.bits.is_empty()
     }
+
+    /// Returns the indices of the set bits, in increasing order.
+    pub fn set_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bit)| bit.then_some(index))
+    }
+
+    /// Returns the number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().filter(|&&bit| bit).count()
+    }
+
+    /// Returns the bits set in both lists.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Returns the bits set in either list.
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Returns the bits set in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Combines the lists bit by bit. The result is as long as the longer list, with the
+    /// shorter one padded with unset bits, so it stays within `N`.
+    fn combine(&self, other: &Self, f: impl Fn(bool, bool) -> bool) -> Self {
+        let len = self.len().max(other.len());
+        let bit = |bits: &[bool], index: usize| bits.get(index).copied().unwrap_or(false);
+        Self {
+            bits: (0..len)
+                .map(|index| f(bit(&self.bits, index), bit(&other.bits, index)))
+                .collect(),
+        }
+    }
+
+    /// Returns the root of the bit list's data tree, before the length is mixed in.
+    ///
+    /// `hash_tree_root` is `mix_in_length(self.data_tree_root()?, self.len())`.
+    pub fn data_tree_root(&self) -> Result<B256, SSZError> {
+        let mut bytes = vec![0u8; self.len().div_ceil(8)];
+        for index in self.set_indices() {
+            bytes[index / 8] |= 1 << (index % 8);
+        }
+        merkleize(&pack(&bytes), Some(Self::chunk_count()))
+    }
+
+    /// Deserializes a bit list from its SSZ encoding as a hex string.
+    pub fn from_hex(s: &str) -> Result<Self, SSZError> {
+        crate::hex_util::from_hex(s)
+    }
+
+    /// Serializes the bit list and encodes it as a `0x`-prefixed hex string.
+    pub fn to_hex(&self) -> Result<String, SSZError> {
+        crate::hex_util::to_hex(self)
+    }
 }
 
 impl<const N: usize> SszTypeInfo for BitList<N> {
-    /// Returns false since not fixed size.
-    fn is_fixed_size() -> bool {
-        false
-    }
+    /// Not fixed size.
+    const IS_FIXED: bool = false;
 
     /// Returns the size of the type in bytes.
     fn fixed_size() -> Option<usize> {
@@ -86,7 +169,7 @@ impl<const N: usize> SimpleSerialize for BitList<N> {
             });
         }
 
-        let byte_len = bit_len.div_ceil(8) + 1;
+        let byte_len = bit_len / 8 + 1;
         let mut bytes = vec![0u8; byte_len];
 
         for (i, &bit) in self.bits.iter().enumerate() {
@@ -104,7 +187,7 @@ impl<const N: usize> SimpleSerialize for BitList<N> {
 }
 
 impl<const N: usize> SimpleDeserialize for BitList<N> {
-    /// Deserializes a bit list.    
+    /// Deserializes a bit list.
     fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
         if data.is_empty() {
             return Err(SSZError::InvalidLength {
@@ -118,37 +201,18 @@ impl<const N: usize> SimpleDeserialize for BitList<N> {
             return Err(SSZError::OffsetOutOfBounds);
         }
 
-        let mut bits = Vec::new();
-        let total_bits = data.len() * 8;
-        let mut _found_delimiter = false;
-        let mut logical_bits = 0;
-
-        #[allow(unused_labels)]
-        'outer: for (i, byte) in data.iter().enumerate() {
-            for j in 0..8 {
-                let global_bit_index = i * 8 + j;
-                if global_bit_index >= total_bits {
-                    break;
-                }
-                if (byte >> j) & 1 != 0 {
-                    logical_bits = global_bit_index;
-                }
-            }
-        }
-
-        for i in 0..logical_bits {
-            let byte = data[i / 8];
-            let bit = (byte >> (i % 8)) & 1;
-            bits.push(bit == 1);
-        }
-
-        if bits.len() > N {
+        // The delimiter is the highest set bit of the last byte; every bit below it is data.
+        let len = (data.len() - 1) * 8 + (7 - last.leading_zeros() as usize);
+        if len > N {
             return Err(SSZError::InvalidLength {
                 expected: N,
-                got: bits.len(),
+                got: len,
             });
         }
 
+        let bits = (0..len)
+            .map(|i| (data[i / 8] >> (i % 8)) & 1 == 1)
+            .collect();
         Ok(Self { bits })
     }
 }
@@ -156,27 +220,21 @@ impl<const N: usize> SimpleDeserialize for BitList<N> {
 /// Calculates `hash_tree_root` for BitList.
 impl<const N: usize> Merkleize for BitList<N> {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        let bit_count = self.len();
-
-        let byte_count = bit_count.div_ceil(8);
-        let mut bytes = vec![0u8; byte_count];
-        for (i, &bit) in self.bits.iter().enumerate() {
-            if bit {
-                bytes[i / 8] |= 1 << (i % 8);
-            }
-        }
-
-        let chunks = pack(&bytes);
-
-        let limit = N.div_ceil(256);
-        let root = merkleize(&chunks, Some(limit)).expect("merkleize");
-        let final_root = mix_in_length(root, bit_count);
-        Ok(final_root)
+        Ok(mix_in_length(self.data_tree_root()?, self.len()))
     }
 
     fn chunk_count() -> usize {
         N.div_ceil(256)
     }
+
+    /// Adds the length mix-in layer.
+    fn merkle_depth() -> u32 {
+        get_depth(Self::chunk_count()) + 1
+    }
+
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Length
+    }
 }
 
 #[cfg(test)]
@@ -186,6 +244,21 @@ mod tests {
     use alloc::string::ToString;
     use alloy_primitives::hex::FromHex;
 
+    #[test]
+    fn test_bitlist_data_tree_root() {
+        let bl = BitList::<300>::from_bools(&[true, false, true, true]).unwrap();
+        assert_eq!(
+            mix_in_length(bl.data_tree_root().unwrap(), bl.len()),
+            bl.hash_tree_root().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bitlist_set_indices() {
+        let bl = BitList::<16>::from_bools(&[true, false, false, true, false]).unwrap();
+        assert_eq!(bl.set_indices().collect::<Vec<_>>(), vec![0, 3]);
+    }
+
     #[test]
     fn test_bitlist_edge_cases() {
         let empty: BitList<32> = BitList::default();
@@ -237,6 +310,142 @@ mod tests {
         assert!(matches!(result, Err(SSZError::OffsetOutOfBounds)));
     }
 
+    #[test]
+    fn test_bitlist_deserialize_delimiter() {
+        let cases: [(&[u8], &[bool]); 5] = [
+            (&[1], &[]),
+            (&[0b1101], &[true, false, true]),
+            (&[0xff, 0x01], &[true; 8]),
+            (
+                &[0x00, 0b110],
+                &[
+                    false, false, false, false, false, false, false, false, false, true,
+                ],
+            ),
+            (
+                &[0b1000_0001, 0b1000_0000],
+                &[
+                    true, false, false, false, false, false, false, true, false, false, false,
+                    false, false, false, false,
+                ],
+            ),
+        ];
+        for (bytes, bools) in cases {
+            let decoded = BitList::<16>::deserialize(bytes).expect("can decode");
+            assert_eq!(decoded, BitList::<16>::from_bools(bools).unwrap());
+
+            let mut buffer = vec![];
+            decoded.serialize(&mut buffer).expect("can encode");
+            assert_eq!(buffer, bytes);
+        }
+
+        assert_eq!(
+            BitList::<2>::deserialize(&[0b1101]),
+            Err(SSZError::InvalidLength {
+                expected: 2,
+                got: 3
+            })
+        );
+        assert_eq!(
+            BitList::<16>::deserialize(&[0b1101, 0]),
+            Err(SSZError::OffsetOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_bitlist_set_operations() {
+        let a = BitList::<8>::from_bools(&[true, true, false, true, true]).unwrap();
+        let b = BitList::<8>::from_bools(&[true, false, true]).unwrap();
+
+        assert_eq!(
+            a.intersection(&b),
+            BitList::from_bools(&[true, false, false, false, false]).unwrap()
+        );
+        assert_eq!(
+            a.union(&b),
+            BitList::from_bools(&[true, true, true, true, true]).unwrap()
+        );
+        assert_eq!(
+            a.difference(&b),
+            BitList::from_bools(&[false, true, false, true, true]).unwrap()
+        );
+        assert_eq!(
+            b.difference(&a),
+            BitList::from_bools(&[false, false, true, false, false]).unwrap()
+        );
+        // Operations are symmetric in length: the shorter list is padded either way.
+        assert_eq!(b.union(&a), a.union(&b));
+        assert_eq!(b.intersection(&a).len(), 5);
+
+        assert_eq!(a.count_ones(), 4);
+        assert_eq!(a.union(&b).count_ones(), 5);
+        assert_eq!(a.intersection(&BitList::new()).count_ones(), 0);
+        assert_eq!(
+            a.union(&b).set_indices().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_bitlist_from_bools() {
+        let bools = [true, false, true, true, false, false, true, false];
+        let list = BitList::<8>::from_bools(&bools).expect("fits within limit");
+        assert_eq!(list.len(), 8);
+        assert_eq!(list, BitList::try_from(bools.as_ref()).unwrap());
+
+        let mut buffer = vec![];
+        list.serialize(&mut buffer).expect("can encode");
+        assert_eq!(buffer, vec![0b0100_1101, 0x01]);
+
+        let result = BitList::<4>::from_bools(&bools);
+        assert!(matches!(
+            result,
+            Err(SSZError::InvalidLength {
+                expected: 4,
+                got: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bitlist_extend_from_bytes() {
+        let mut list = BitList::<16>::default();
+        list.push(true).unwrap();
+        list.extend_from_bytes(&[0b1010_0101, 0b0000_1001], 12)
+            .expect("fits within limit");
+        let expected = BitList::try_from(
+            [
+                true, true, false, true, false, false, true, false, true, true, false, false, true,
+            ]
+            .as_ref(),
+        )
+        .unwrap();
+        assert_eq!(list, expected);
+
+        let mut buffer = vec![];
+        list.serialize(&mut buffer).expect("can encode");
+        assert_eq!(BitList::<16>::deserialize(&buffer).unwrap(), expected);
+
+        let result = list.extend_from_bytes(&[0xff], 4);
+        assert!(matches!(
+            result,
+            Err(SSZError::InvalidLength {
+                expected: 16,
+                got: 17
+            })
+        ));
+        assert_eq!(list.len(), 13);
+
+        let result = BitList::<16>::default().extend_from_bytes(&[0xff], 12);
+        assert!(matches!(
+            result,
+            Err(SSZError::InvalidByteLength {
+                expected: 2,
+                got: 1
+            })
+        ));
+    }
+
     #[test]
     fn test_bitlist_serialize() {
         let value: BitList<10> = BitList::default();
@@ -250,7 +459,7 @@ mod tests {
         let _ = value.push(false);
         let _ = value.push(true);
         (value).serialize(&mut buffer).expect("can encode");
-        let expected = [6u8, 0u8];
+        let expected = [6u8];
         assert_eq!(buffer, expected);
 
         let mut buffer = vec![];
@@ -304,6 +513,17 @@ mod tests {
         assert_eq!(result.to_string(), expected.to_string());
     }
 
+    #[test]
+    fn test_bitlist_hex() {
+        let list = BitList::<32>::from_hex("0x1801").expect("valid hex");
+        let expected =
+            BitList::try_from([false, false, false, true, true, false, false, false].as_ref())
+                .unwrap();
+        assert_eq!(list, expected);
+        assert_eq!(list.to_hex().unwrap(), "0x1801");
+        assert!(BitList::<32>::from_hex("0x").is_err());
+    }
+
     #[test]
     fn roundtrip_bitlist() {
         let input = BitList::<32>::try_from(
@@ -321,6 +541,37 @@ mod tests {
         assert_eq!(input, recovered);
     }
 
+    #[test]
+    fn roundtrip_bitlist_full_length_on_byte_boundary() {
+        // With exactly N bits on a byte boundary the delimiter needs a whole extra byte.
+        let input = BitList::<32>::from_vec(vec![true; 32]).unwrap();
+        let mut buffer = vec![];
+        input.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(buffer, vec![0xff, 0xff, 0xff, 0xff, 0x01]);
+        let recovered = BitList::<32>::deserialize(&buffer).expect("can decode");
+        assert_eq!(recovered.len(), 32);
+        assert_eq!(input, recovered);
+
+        let input =
+            BitList::<8>::try_from([true, false, false, true, false, true, true, false].as_ref())
+                .unwrap();
+        let mut buffer = vec![];
+        input.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(buffer, vec![0b0110_1001, 0x01]);
+        let recovered = BitList::<8>::deserialize(&buffer).expect("can decode");
+        assert_eq!(recovered.len(), 8);
+        assert_eq!(input, recovered);
+
+        let result = BitList::<8>::deserialize(&[0xff, 0x02]);
+        assert!(matches!(
+            result,
+            Err(SSZError::InvalidLength {
+                expected: 8,
+                got: 9
+            })
+        ));
+    }
+
     #[test]
     fn test_bitlist_merkleization() {
         let empty: BitList<32> = BitList::default();
@@ -354,6 +605,9 @@ mod tests {
         assert_eq!(BitList::<257>::chunk_count(), 2);
         assert_eq!(BitList::<512>::chunk_count(), 2);
         assert_eq!(BitList::<513>::chunk_count(), 3);
+
+        assert_eq!(BitList::<256>::merkle_depth(), 1);
+        assert_eq!(BitList::<513>::merkle_depth(), 3);
     }
     #[test]
     fn test_bitlist_9_merkleization_example() {
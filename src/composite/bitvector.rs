@@ -4,9 +4,12 @@ use crate::{
     Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
     merkleization::{merkleize, pack_bits},
 };
+use alloc::format;
+use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 use alloy_primitives::B256;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
 use core::{option::Option, result::Result};
 
 #[derive(Debug, PartialEq)]
@@ -31,18 +34,26 @@ impl<const N: usize> BitVector<N> {
         self.bits.get(index).copied()
     }
 
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         N
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.bits.is_empty()
+    pub const fn is_empty(&self) -> bool {
+        N == 0
     }
 
     pub fn as_bits(&self) -> &[bool] {
         &self.bits
     }
 
+    /// Returns the indices of the set bits, in increasing order.
+    pub fn set_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bit)| bit.then_some(index))
+    }
+
     pub fn set(&mut self, index: usize, value: bool) -> Result<(), SSZError> {
         if index >= N {
             return Err(SSZError::InvalidLength {
@@ -54,24 +65,40 @@ impl<const N: usize> BitVector<N> {
         Ok(())
     }
 
+    /// Builds a bit vector from its first `bools.len()` bits, leaving the rest unset.
+    ///
+    /// Fails if there are more than `N` bits.
     pub fn from_bools(bools: &[bool]) -> Result<Self, SSZError> {
-        if bools.len() != N {
+        if bools.len() > N {
             return Err(SSZError::InvalidLength {
                 expected: N,
                 got: bools.len(),
             });
         }
-        Ok(Self {
-            bits: bools.to_vec(),
-        })
+        let mut bv = Self::new();
+        bv.bits[..bools.len()].copy_from_slice(bools);
+        Ok(bv)
+    }
+
+    /// Returns the number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().filter(|&&bit| bit).count()
+    }
+
+    /// Deserializes a bit vector from its SSZ encoding as a hex string.
+    pub fn from_hex(s: &str) -> Result<Self, SSZError> {
+        crate::hex_util::from_hex(s)
+    }
+
+    /// Serializes the bit vector and encodes it as a `0x`-prefixed hex string.
+    pub fn to_hex(&self) -> Result<String, SSZError> {
+        crate::hex_util::to_hex(self)
     }
 }
 
 impl<const N: usize> SszTypeInfo for BitVector<N> {
     /// Indicates that the bit vector is fixed-size.
-    fn is_fixed_size() -> bool {
-        true
-    }
+    const IS_FIXED: bool = true;
 
     /// Returns the fixed size of the bit vector in bytes.
     fn fixed_size() -> Option<usize> {
@@ -94,16 +121,24 @@ impl<const N: usize> SimpleSerialize for BitVector<N> {
         buffer.extend_from_slice(&bytes);
         Ok(byte_length)
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(N.div_ceil(8))
+    }
 }
 
 impl<const N: usize> SimpleDeserialize for BitVector<N> {
     /// Deserializes a bit vector.
     fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        // A bitvector's byte length is fixed by `N`, so any other length is input for a
+        // different type rather than a truncated value.
         let expected_bytes = N.div_ceil(8);
         if data.len() != expected_bytes {
-            return Err(SSZError::InvalidLength {
-                expected: expected_bytes,
-                got: data.len(),
+            return Err(SSZError::TypeMismatch {
+                context: format!(
+                    "BitVector<{N}> needs {expected_bytes} bytes, got {}",
+                    data.len()
+                ),
             });
         }
 
@@ -118,6 +153,57 @@ impl<const N: usize> SimpleDeserialize for BitVector<N> {
     }
 }
 
+/// Implements a bitwise operator for `BitVector<N>`, by value and by reference.
+macro_rules! impl_bitwise_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl<const N: usize> $trait for &BitVector<N> {
+            type Output = BitVector<N>;
+
+            fn $method(self, rhs: Self) -> BitVector<N> {
+                BitVector {
+                    bits: self
+                        .bits
+                        .iter()
+                        .zip(&rhs.bits)
+                        .map(|(&a, &b)| a $op b)
+                        .collect(),
+                }
+            }
+        }
+
+        impl<const N: usize> $trait for BitVector<N> {
+            type Output = BitVector<N>;
+
+            fn $method(self, rhs: Self) -> BitVector<N> {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_bitwise_op!(BitAnd, bitand, &);
+impl_bitwise_op!(BitOr, bitor, |);
+impl_bitwise_op!(BitXor, bitxor, ^);
+
+/// Flips each of the `N` bits; the padding bits of the encoding stay zero.
+impl<const N: usize> Not for &BitVector<N> {
+    type Output = BitVector<N>;
+
+    fn not(self) -> BitVector<N> {
+        BitVector {
+            bits: self.bits.iter().map(|&bit| !bit).collect(),
+        }
+    }
+}
+
+impl<const N: usize> Not for BitVector<N> {
+    type Output = BitVector<N>;
+
+    fn not(self) -> BitVector<N> {
+        !&self
+    }
+}
+
 /// Implements `hash_tree_root` for BitVector
 impl<const N: usize> Merkleize for BitVector<N> {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
@@ -145,6 +231,68 @@ mod tests {
     use super::*;
     use alloy_primitives::hex;
 
+    #[test]
+    fn test_bitvector_len() {
+        assert_eq!(BitVector::<8>::new().len(), 8);
+        assert!(!BitVector::<8>::new().is_empty());
+        assert!(BitVector::<0>::new().is_empty());
+    }
+
+    #[test]
+    fn test_bitvector_set_indices() {
+        let mut bv = BitVector::<8>::new();
+        bv.set(3, true).unwrap();
+        bv.set(7, true).unwrap();
+        assert_eq!(bv.set_indices().collect::<Vec<_>>(), vec![3, 7]);
+        assert_eq!(BitVector::<8>::new().set_indices().count(), 0);
+    }
+
+    #[test]
+    fn test_bitvector_from_bools() {
+        let bv = BitVector::<4>::from_bools(&[true, false, true, true]).unwrap();
+        assert_eq!(bv.as_bits(), &[true, false, true, true]);
+        assert_eq!(bv.get(0), Some(true));
+        assert_eq!(bv.get(1), Some(false));
+        assert_eq!(bv.get(4), None);
+        assert_eq!(bv.count_ones(), 3);
+
+        // Missing trailing bits are unset.
+        let short = BitVector::<4>::from_bools(&[true]).unwrap();
+        assert_eq!(short.as_bits(), &[true, false, false, false]);
+        assert_eq!(short.count_ones(), 1);
+        assert_eq!(BitVector::<4>::from_bools(&[]).unwrap(), BitVector::new());
+        assert_eq!(BitVector::<4>::new().count_ones(), 0);
+
+        assert_eq!(
+            BitVector::<4>::from_bools(&[false; 5]),
+            Err(SSZError::InvalidLength {
+                expected: 4,
+                got: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_bitvector_bitwise_ops() {
+        fn encode(bv: &BitVector<12>) -> Vec<u8> {
+            let mut buffer = vec![];
+            bv.serialize(&mut buffer).unwrap();
+            buffer
+        }
+
+        let a = BitVector::<12>::deserialize(&[0b1010_1100, 0b0000_1001]).unwrap();
+        let b = BitVector::<12>::deserialize(&[0b0110_0101, 0b0000_0011]).unwrap();
+
+        assert_eq!(encode(&(&a & &b)), vec![0b0010_0100, 0b0000_0001]);
+        assert_eq!(encode(&(&a | &b)), vec![0b1110_1101, 0b0000_1011]);
+        assert_eq!(encode(&(&a ^ &b)), vec![0b1100_1001, 0b0000_1010]);
+        // Only the 12 bits are flipped; the top four padding bits stay zero.
+        assert_eq!(encode(&!&a), vec![0b0101_0011, 0b0000_0110]);
+
+        assert_eq!(!!BitVector::<12>::new(), BitVector::new());
+        assert_eq!((!&a & a).count_ones(), 0);
+    }
+
     #[test]
     fn test_bitvector_serialize() {
         let mut buffer = vec![];
@@ -167,6 +315,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bitvector_hex() {
+        let bv = BitVector::<8>::from_hex("18").expect("valid hex");
+        assert_eq!(
+            bv.as_bits(),
+            &[false, false, false, true, true, false, false, false]
+        );
+        assert_eq!(bv.to_hex().unwrap(), "0x18");
+        assert_eq!(BitVector::<8>::from_hex("0x18").unwrap(), bv);
+
+        assert!(BitVector::<8>::from_hex("zz").is_err());
+        assert!(BitVector::<8>::from_hex("1800").is_err());
+    }
+
     #[test]
     fn test_invalid_length() {
         assert!(BitVector::<8>::deserialize(&[0, 0]).is_err());
@@ -174,6 +336,18 @@ mod tests {
         assert!(bv.set(8, true).is_err());
     }
 
+    #[test]
+    fn test_bitvector_type_mismatch() {
+        // Three bytes can only ever be a BitVector of 17 to 24 bits.
+        assert_eq!(
+            BitVector::<8>::deserialize(&[0xff, 0x00, 0x01]),
+            Err(SSZError::TypeMismatch {
+                context: "BitVector<8> needs 1 bytes, got 3".into()
+            })
+        );
+        assert!(BitVector::<24>::deserialize(&[0xff, 0x00, 0x01]).is_ok());
+    }
+
     #[test]
     fn roundtrip_test() {
         let mut buffer = vec![];
@@ -0,0 +1,190 @@
+//! Serialization , deserialization and merkleization for byte lists.
+
+use crate::{
+    BYTES_PER_CHUNK, Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize,
+    SszTypeInfo,
+    merkleization::{get_depth, merkleize, mix_in_length, pack},
+};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use core::ops::Deref;
+
+/// SSZ `List[byte, N]` holding at most `N` bytes.
+///
+/// Encodes and merkleizes like [`List<u8, N>`](crate::ssz_list::List), with byte-slice
+/// constructors and accessors.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ByteList<const N: usize>(Vec<u8>);
+
+impl<const N: usize> ByteList<N> {
+    /// Copies `bytes` into a byte list, failing if there are more than `N`.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, SSZError> {
+        Self::try_from(bytes.to_vec())
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Decodes a byte list from a hex string, with or without a `0x` prefix.
+    pub fn from_hex(s: &str) -> Result<Self, SSZError> {
+        crate::hex_util::from_hex(s)
+    }
+
+    /// Encodes the bytes as a `0x`-prefixed hex string.
+    pub fn to_hex(&self) -> Result<String, SSZError> {
+        crate::hex_util::to_hex(self)
+    }
+}
+
+impl<const N: usize> TryFrom<Vec<u8>> for ByteList<N> {
+    type Error = SSZError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, SSZError> {
+        if bytes.len() > N {
+            return Err(SSZError::InvalidLength {
+                expected: N,
+                got: bytes.len(),
+            });
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl<const N: usize> Deref for ByteList<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> SszTypeInfo for ByteList<N> {
+    const IS_FIXED: bool = false;
+
+    fn fixed_size() -> Option<usize> {
+        None
+    }
+}
+
+/// Serializes `ByteList<N>` as its raw bytes.
+impl<const N: usize> SimpleSerialize for ByteList<N> {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        buffer.extend_from_slice(&self.0);
+        Ok(self.0.len())
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(self.0.len())
+    }
+}
+
+/// Deserializes `ByteList<N>`, rejecting more than `N` bytes.
+impl<const N: usize> SimpleDeserialize for ByteList<N> {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        Self::from_slice(data)
+    }
+}
+
+/// Merkleizes `ByteList<N>` as packed chunks padded to `N.div_ceil(32)`, then mixes in the
+/// length.
+impl<const N: usize> Merkleize for ByteList<N> {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        let root = merkleize(&pack(&self.0), Some(Self::chunk_count()))?;
+        Ok(mix_in_length(root, self.0.len()))
+    }
+
+    fn chunk_count() -> usize {
+        N.div_ceil(BYTES_PER_CHUNK)
+    }
+
+    /// Adds the length mix-in layer.
+    fn merkle_depth() -> u32 {
+        get_depth(Self::chunk_count()) + 1
+    }
+
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ssz_list::List;
+    use alloc::vec;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_byte_list_roundtrip() {
+        let list = ByteList::<64>::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(list.as_slice(), &[1, 2, 3]);
+        assert_eq!(list.len(), 3);
+
+        let mut buffer = vec![];
+        assert_eq!(list.serialize(&mut buffer), Ok(3));
+        assert_eq!(buffer, vec![1, 2, 3]);
+        assert_eq!(ByteList::<64>::deserialize(&buffer), Ok(list));
+
+        let empty = ByteList::<64>::default();
+        let mut buffer = vec![];
+        empty.serialize(&mut buffer).unwrap();
+        assert!(buffer.is_empty());
+        assert_eq!(ByteList::<64>::deserialize(&buffer), Ok(empty));
+
+        assert_eq!(
+            ByteList::<2>::deserialize(&[1, 2, 3]),
+            Err(SSZError::InvalidLength {
+                expected: 2,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_byte_list_hex() {
+        let list = ByteList::<4>::from_hex("0x0102ff").expect("valid hex");
+        assert_eq!(list.as_slice(), &[1, 2, 0xff]);
+        assert_eq!(list.to_hex().unwrap(), "0x0102ff");
+        assert_eq!(ByteList::<4>::from_hex(&list.to_hex().unwrap()), Ok(list));
+
+        assert_eq!(ByteList::<4>::from_hex("").unwrap().to_hex().unwrap(), "0x");
+        assert!(ByteList::<4>::from_hex("0x0102030405").is_err());
+        assert!(ByteList::<4>::from_hex("0xzz").is_err());
+    }
+
+    #[test]
+    fn test_byte_list_root() {
+        let list = ByteList::<64>::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(
+            list.hash_tree_root().unwrap(),
+            B256::from(hex!(
+                "fffcfed8f2dc38855289d2d44e82bc2adfeb34ab8481542ecaff6886e8be5c1a"
+            ))
+        );
+        assert_eq!(
+            list.hash_tree_root().unwrap(),
+            List::<u8, 64>::new(vec![1, 2, 3])
+                .unwrap()
+                .hash_tree_root()
+                .unwrap()
+        );
+
+        // An empty list is a zero chunk with length 0 mixed in.
+        assert_eq!(
+            ByteList::<32>::default().hash_tree_root().unwrap(),
+            mix_in_length(B256::ZERO, 0)
+        );
+        assert_eq!(
+            ByteList::<32>::default().hash_tree_root().unwrap(),
+            B256::from(hex!(
+                "f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"
+            ))
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! Serialization , deserialization and merkleization for Bytes.
+
+use crate::{Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo};
+use alloc::vec::Vec;
+use alloy_primitives::{B256, Bytes};
+
+/// SSZTypeInfo implementation for Bytes
+impl SszTypeInfo for Bytes {
+    const IS_FIXED: bool = false;
+
+    fn fixed_size() -> Option<usize> {
+        None
+    }
+}
+
+/// Serializes `Bytes` as raw bytes, same as `Vec<u8>`.
+impl SimpleSerialize for Bytes {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        buffer.extend_from_slice(self);
+        Ok(self.len())
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(self.len())
+    }
+}
+
+impl SimpleDeserialize for Bytes {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        Ok(Bytes::copy_from_slice(data))
+    }
+}
+
+/// Merkleizes `Bytes` exactly like the equivalent `Vec<u8>`.
+impl Merkleize for Bytes {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        self.to_vec().hash_tree_root()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let original = Bytes::from(vec![1u8, 2, 3, 4]);
+        let mut buffer = vec![];
+        let written = original.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(written, 4);
+        assert_eq!(buffer, vec![1, 2, 3, 4]);
+
+        let decoded = Bytes::deserialize(&buffer).expect("can deserialize");
+        assert_eq!(decoded, original);
+
+        let empty = Bytes::new();
+        let mut buffer = vec![];
+        empty.serialize(&mut buffer).expect("can serialize empty");
+        assert!(buffer.is_empty());
+        assert_eq!(Bytes::deserialize(&buffer).unwrap(), empty);
+    }
+
+    #[test]
+    fn test_bytes_root_matches_vec() {
+        let bytes = Bytes::from(vec![1u8, 2, 3, 4]);
+        let vec = vec![1u8, 2, 3, 4];
+        assert_eq!(
+            bytes.hash_tree_root().unwrap(),
+            vec.hash_tree_root().unwrap()
+        );
+    }
+}
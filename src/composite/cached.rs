@@ -0,0 +1,197 @@
+//! Memoized `hash_tree_root` for any SSZ value.
+
+use crate::{Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo};
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use core::ops::{Deref, DerefMut};
+
+// With `rayon`, values are shared across worker threads, so the root cell must be `Sync`.
+#[cfg(not(feature = "rayon"))]
+use core::cell::OnceCell as RootCell;
+#[cfg(feature = "rayon")]
+use std::sync::OnceLock as RootCell;
+
+/// Wraps an SSZ value and remembers its `hash_tree_root` until the value is mutated.
+///
+/// Any mutable access through `DerefMut` drops the remembered root, so a `push`, `set` or
+/// index assignment on the wrapped value is always followed by a fresh computation. Encoding
+/// is unchanged: `Cached<T>` serializes exactly like `T`.
+///
+/// Without the `rayon` feature the root is kept in a `core::cell::OnceCell`, so `Cached<T>`
+/// is `Send` but not `Sync` and can't be shared between threads by reference. With `rayon` it
+/// uses `std::sync::OnceLock` and is `Sync` whenever `T` is.
+#[derive(Debug, Clone, Default)]
+pub struct Cached<T> {
+    value: T,
+    root: RootCell<B256>,
+}
+
+impl<T> Cached<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            root: RootCell::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the remembered root, if it has been computed since the last mutation.
+    pub fn cached_root(&self) -> Option<B256> {
+        self.root.get().copied()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Cached<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Deref for Cached<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Cached<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.root.take();
+        &mut self.value
+    }
+}
+
+impl<T: SszTypeInfo> SszTypeInfo for Cached<T> {
+    const IS_FIXED: bool = T::IS_FIXED;
+
+    fn fixed_size() -> Option<usize> {
+        T::fixed_size()
+    }
+
+    fn is_basic_type() -> bool {
+        T::is_basic_type()
+    }
+}
+
+impl<T: SimpleSerialize> SimpleSerialize for Cached<T> {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        self.value.serialize(buffer)
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        self.value.ssz_bytes_len()
+    }
+}
+
+impl<T: SimpleDeserialize> SimpleDeserialize for Cached<T> {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        T::deserialize(data).map(Self::new)
+    }
+}
+
+/// Returns the remembered root, computing and remembering it first if needed. Errors are not
+/// remembered.
+impl<T: Merkleize> Merkleize for Cached<T> {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        if let Some(root) = self.root.get() {
+            return Ok(*root);
+        }
+        let root = self.value.hash_tree_root()?;
+        Ok(*self.root.get_or_init(|| root))
+    }
+
+    fn chunk_count() -> usize {
+        T::chunk_count()
+    }
+
+    fn merkle_depth() -> u32 {
+        T::merkle_depth()
+    }
+
+    fn mix_in_kind() -> MixInKind {
+        T::mix_in_kind()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ssz_list::List;
+    use alloc::vec;
+    use core::cell::Cell;
+
+    fn assert_send<T: Send>() {}
+
+    #[cfg(feature = "rayon")]
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn test_cached_thread_safety() {
+        assert_send::<Cached<List<u64, 8>>>();
+        #[cfg(feature = "rayon")]
+        assert_sync::<Cached<List<u64, 8>>>();
+    }
+
+    struct Counting {
+        value: u64,
+        computed: Cell<usize>,
+    }
+
+    impl Merkleize for Counting {
+        fn hash_tree_root(&self) -> Result<B256, SSZError> {
+            self.computed.set(self.computed.get() + 1);
+            self.value.hash_tree_root()
+        }
+    }
+
+    #[test]
+    fn test_cached_root_is_reused_until_mutation() {
+        let mut cached = Cached::new(Counting {
+            value: 7,
+            computed: Cell::new(0),
+        });
+        assert_eq!(cached.cached_root(), None);
+
+        let root = cached.hash_tree_root().unwrap();
+        assert_eq!(cached.hash_tree_root().unwrap(), root);
+        assert_eq!(cached.computed.get(), 1);
+        assert_eq!(cached.cached_root(), Some(root));
+
+        (*cached).value = 8;
+        assert_eq!(cached.cached_root(), None);
+        assert_eq!(
+            cached.hash_tree_root().unwrap(),
+            8u64.hash_tree_root().unwrap()
+        );
+        assert_eq!(cached.computed.get(), 2);
+    }
+
+    #[test]
+    fn test_cached_list_invalidated_by_push_and_index() {
+        let mut cached = Cached::new(List::<u64, 8>::new(vec![1, 2]).unwrap());
+        let before = cached.hash_tree_root().unwrap();
+
+        cached.push_and_root(3).unwrap();
+        assert_eq!(cached.cached_root(), None);
+        let pushed = cached.hash_tree_root().unwrap();
+        assert_ne!(pushed, before);
+
+        cached[0] = 9;
+        assert_eq!(cached.cached_root(), None);
+        assert_eq!(
+            cached.hash_tree_root().unwrap(),
+            List::<u64, 8>::new(vec![9, 2, 3])
+                .unwrap()
+                .hash_tree_root()
+                .unwrap()
+        );
+
+        let mut buffer = vec![];
+        cached.serialize(&mut buffer).unwrap();
+        assert_eq!(Cached::<List<u64, 8>>::deserialize(&buffer), Ok(cached));
+    }
+}
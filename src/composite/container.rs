@@ -1,45 +1,362 @@
 //! Serializes , deserializes and merkleization of container.
+//!
+//! Structs with named fields get the SSZ traits from the derives re-exported at the crate root:
+//!
+//! ```
+//! use r_ssz::{Merkleize, SimpleDeserialize, SimpleSerialize, SszTypeInfo};
+//!
+//! #[derive(Debug, PartialEq, SimpleSerialize, SimpleDeserialize, SszTypeInfo, Merkleize)]
+//! pub struct Block {
+//!     pub slot: u64,
+//!     pub body: Vec<u8>,
+//! }
+//!
+//! let block = Block { slot: 1, body: vec![9] };
+//! let mut buffer = vec![];
+//! block.serialize(&mut buffer).unwrap();
+//! assert_eq!(buffer, vec![1, 0, 0, 0, 0, 0, 0, 0, 12, 0, 0, 0, 9]);
+//! assert_eq!(Block::deserialize(&buffer), Ok(block));
+//! assert_eq!(Block::chunk_count(), 2);
+//! ```
 
-use crate::SimpleDeserialize;
 use crate::error::SSZError;
-use crate::ssz::SimpleSerialize;
+use crate::field_path::{FieldPath, container_generalized_index};
+use crate::merkleization::merkleize_fields;
+use crate::ssz::{Merkleize, SimpleSerialize, SszTypeInfo};
+use crate::{BITS_PER_BYTE, BYTES_PER_LENGTH_OFFSET};
+use crate::{SimpleDeserialize, SimpleDeserializeRef};
 use alloc::vec::Vec;
+use alloy_primitives::B256;
+
+/// Serializes container fields in order, interleaving offsets for variable-size fields.
+///
+/// Each field is paired with whether its type is fixed-size. The total length is computed up
+/// front with `ssz_bytes_len`, so the buffer is reserved once and the fixed section, offsets
+/// and variable section are written in place without intermediate buffers.
+///
+/// This relies on every field's `serialize` writing exactly `ssz_bytes_len` bytes in both
+/// passes; otherwise the offsets written up front would point into the wrong place. Debug
+/// builds assert that the written sections match the lengths computed in the first pass.
+pub fn serialize_fields<'a, I>(fields: I, buffer: &mut Vec<u8>) -> Result<usize, SSZError>
+where
+    I: IntoIterator<Item = (&'a dyn SimpleSerialize, bool)>,
+    I::IntoIter: Clone,
+{
+    let fields = fields.into_iter();
+
+    let mut fixed_len = 0;
+    let mut variable_len = 0;
+    for (field, is_fixed) in fields.clone() {
+        if is_fixed {
+            fixed_len += field.ssz_bytes_len()?;
+        } else {
+            fixed_len += BYTES_PER_LENGTH_OFFSET;
+            variable_len += field.ssz_bytes_len()?;
+        }
+    }
+
+    let total_len = fixed_len + variable_len;
+    if total_len as u64 >= 1u64 << (BYTES_PER_LENGTH_OFFSET * BITS_PER_BYTE) {
+        return Err(SSZError::OffsetOutOfBounds);
+    }
+    buffer.reserve(total_len);
+
+    let start_len = buffer.len();
+    let mut offset = fixed_len;
+    for (field, is_fixed) in fields.clone() {
+        if is_fixed {
+            field.serialize(buffer)?;
+        } else {
+            buffer.extend_from_slice(&(offset as u32).to_le_bytes());
+            offset += field.ssz_bytes_len()?;
+        }
+    }
+    debug_assert_eq!(
+        buffer.len() - start_len,
+        fixed_len,
+        "fixed section length changed between passes"
+    );
+    for (field, is_fixed) in fields {
+        if !is_fixed {
+            field.serialize(buffer)?;
+        }
+    }
+    debug_assert_eq!(
+        buffer.len() - start_len,
+        total_len,
+        "variable section length changed between passes"
+    );
+
+    Ok(buffer.len() - start_len)
+}
+
+/// Streams container fields into `writer` with the same layout as [`serialize_fields`].
+///
+/// Offsets come from each field's `ssz_bytes_len`, computed before anything is written, so
+/// no field is buffered; variable-size fields then stream themselves with
+/// `serialize_to_writer`.
+#[cfg(feature = "std")]
+pub fn write_fields<'a, I>(fields: I, writer: &mut dyn std::io::Write) -> std::io::Result<usize>
+where
+    I: IntoIterator<Item = (&'a dyn SimpleSerialize, bool)>,
+    I::IntoIter: Clone,
+{
+    let fields = fields.into_iter();
+
+    let mut fixed_len = 0;
+    let mut variable_len = 0;
+    for (field, is_fixed) in fields.clone() {
+        let len = field.ssz_bytes_len().map_err(std::io::Error::other)?;
+        if is_fixed {
+            fixed_len += len;
+        } else {
+            fixed_len += BYTES_PER_LENGTH_OFFSET;
+            variable_len += len;
+        }
+    }
+    if (fixed_len + variable_len) as u64 >= 1u64 << (BYTES_PER_LENGTH_OFFSET * BITS_PER_BYTE) {
+        return Err(std::io::Error::other(SSZError::OffsetOutOfBounds));
+    }
+
+    let mut written = 0;
+    let mut offset = fixed_len;
+    for (field, is_fixed) in fields.clone() {
+        if is_fixed {
+            written += field.serialize_to_writer(writer)?;
+        } else {
+            writer.write_all(&(offset as u32).to_le_bytes())?;
+            written += BYTES_PER_LENGTH_OFFSET;
+            offset += field.ssz_bytes_len().map_err(std::io::Error::other)?;
+        }
+    }
+    for (field, is_fixed) in fields {
+        if !is_fixed {
+            written += field.serialize_to_writer(writer)?;
+        }
+    }
+    Ok(written)
+}
+
+/// Splits an encoded container into the encodings of its fields, in order.
+///
+/// `field_sizes` holds each field's fixed size, or `None` for a variable-size field, which
+/// is read from the offset table. The first offset must point just past the fixed section,
+/// offsets may not decrease, and a container without variable-size fields must span
+/// exactly its fixed section.
+pub fn split_fields<'a>(
+    data: &'a [u8],
+    field_sizes: &[Option<usize>],
+) -> Result<Vec<&'a [u8]>, SSZError> {
+    let mut fields = Vec::with_capacity(field_sizes.len());
+    let mut offsets = Vec::new();
+    let mut pos = 0;
+    for (field, size) in field_sizes.iter().enumerate() {
+        let len = size.unwrap_or(BYTES_PER_LENGTH_OFFSET);
+        let bytes = data
+            .get(pos..pos + len)
+            .ok_or(SSZError::ExpectedFurtherInput)?;
+        if size.is_none() {
+            offsets.push((field, u32::deserialize(bytes)? as usize));
+        }
+        fields.push(bytes);
+        pos += len;
+    }
+
+    let Some(&(_, first_offset)) = offsets.first() else {
+        if data.len() != pos {
+            return Err(SSZError::InvalidByteLength {
+                expected: pos,
+                got: data.len(),
+            });
+        }
+        return Ok(fields);
+    };
+    if first_offset != pos {
+        return Err(SSZError::OffsetOutOfBounds);
+    }
+
+    for (i, &(field, start)) in offsets.iter().enumerate() {
+        let end = offsets.get(i + 1).map_or(data.len(), |&(_, end)| end);
+        if end > data.len() {
+            return Err(SSZError::OffsetOutOfBounds);
+        }
+        if start > end {
+            return Err(SSZError::InvalidOffsetRange { start, end });
+        }
+        fields[field] = &data[start..end];
+    }
+    Ok(fields)
+}
+
+/// The `SszContainer` trait describes a container by its fields; blanket impls then provide
+/// `SimpleSerialize`, `SimpleDeserialize` and `Merkleize` for it.
+///
+/// The fields are laid out by [`serialize_fields`] and [`split_fields`], honoring each field's
+/// fixed-size flag, and the root merkleizes the field roots. A type implementing this trait
+/// can't also implement those traits itself, e.g. with the derives.
+pub trait SszContainer: Sized {
+    /// Number of fields in the container.
+    const FIELD_COUNT: usize;
+
+    /// Returns the fields in declaration order, each paired with whether its type is
+    /// fixed-size.
+    fn fields(&self) -> impl Iterator<Item = (&dyn SimpleSerialize, bool)> + Clone;
+
+    /// Returns the `hash_tree_root` of each field, in declaration order.
+    fn field_roots(&self) -> Result<Vec<B256>, SSZError>;
+
+    /// Returns each field's fixed size, or `None` for a variable-size field.
+    fn field_sizes() -> Vec<Option<usize>>;
+
+    /// Builds the container from the encodings of its fields, in declaration order.
+    fn from_fields(fields: &[&[u8]]) -> Result<Self, SSZError>;
+}
+
+/// Serializes a container through its [`SszContainer`] fields.
+impl<C: SszContainer> SimpleSerialize for C {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        debug_assert_eq!(self.fields().count(), C::FIELD_COUNT);
+        serialize_fields(self.fields(), buffer)
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        let mut len = 0;
+        for (field, is_fixed) in self.fields() {
+            if !is_fixed {
+                len += BYTES_PER_LENGTH_OFFSET;
+            }
+            len += field.ssz_bytes_len()?;
+        }
+        Ok(len)
+    }
+
+    #[cfg(feature = "std")]
+    fn serialize_to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<usize> {
+        write_fields(self.fields(), writer)
+    }
+}
+
+/// Deserializes a container through its [`SszContainer`] fields.
+impl<C: SszContainer> SimpleDeserialize for C {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        C::from_fields(&split_fields(data, &C::field_sizes())?)
+    }
+}
+
+/// Merkleizes a container through its [`SszContainer`] field roots.
+impl<C: SszContainer> Merkleize for C {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        let roots = self.field_roots()?;
+        debug_assert_eq!(roots.len(), C::FIELD_COUNT);
+        merkleize_fields(roots)
+    }
+
+    /// One chunk per field root.
+    fn chunk_count() -> usize {
+        C::FIELD_COUNT
+    }
+}
+
+/// Implements `serialize_to_writer` for the `SimpleSerialize` derive with [`write_fields`].
+///
+/// Defined here rather than with `#[cfg]` in the generated code, which would check the calling
+/// crate's features instead of this one's.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ssz_container_writer {
+    ($($field:ident: $ty:ty),+) => {
+        fn serialize_to_writer(
+            &self,
+            writer: &mut dyn $crate::__private::io::Write,
+        ) -> $crate::__private::io::Result<usize> {
+            $crate::container::write_fields(
+                [$((
+                    &self.$field as &dyn $crate::SimpleSerialize,
+                    <$ty as $crate::SszTypeInfo>::IS_FIXED,
+                )),+],
+                writer,
+            )
+        }
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ssz_container_writer {
+    ($($field:ident: $ty:ty),+) => {};
+}
+
+/// Returns the indices at which two containers' per-field roots differ.
+pub fn diff_field_roots(roots: &[B256], other_roots: &[B256]) -> Vec<usize> {
+    roots
+        .iter()
+        .zip(other_roots)
+        .enumerate()
+        .filter_map(|(i, (root, other))| (root != other).then_some(i))
+        .collect()
+}
 
 /// Basic container for testing.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Foo {
     pub a: u32,
     pub b: u8,
 }
 
-/// Serialization of `Foo`.
-impl SimpleSerialize for Foo {
-    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-        let mut written = 0;
-        written += self.a.serialize(buffer)?;
-        written += self.b.serialize(buffer)?;
-        Ok(written)
+impl Foo {
+    /// Returns the indices of the fields whose `hash_tree_root` differs from `other`'s.
+    pub fn field_diff(&self, other: &Self) -> Result<Vec<usize>, SSZError> {
+        Ok(diff_field_roots(
+            &[self.a.hash_tree_root()?, self.b.hash_tree_root()?],
+            &[other.a.hash_tree_root()?, other.b.hash_tree_root()?],
+        ))
     }
 }
 
-/// Deserialization of `Foo`.
-impl SimpleDeserialize for Foo {
-    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if data.len() < 5 {
-            return Err(SSZError::ExpectedFurtherInput);
-        }
+/// Fields of `Foo`; serialization, deserialization and merkleization come from the blanket
+/// impls over `SszContainer`.
+impl SszContainer for Foo {
+    const FIELD_COUNT: usize = 2;
 
-        let a = u32::deserialize(&data[0..4])?;
-        let b = u8::deserialize(&data[4..5])?;
-        Ok(Foo { a, b })
+    fn fields(&self) -> impl Iterator<Item = (&dyn SimpleSerialize, bool)> + Clone {
+        [
+            (&self.a as &dyn SimpleSerialize, u32::IS_FIXED),
+            (&self.b as &dyn SimpleSerialize, u8::IS_FIXED),
+        ]
+        .into_iter()
+    }
+
+    fn field_roots(&self) -> Result<Vec<B256>, SSZError> {
+        Ok(alloc::vec![
+            self.a.hash_tree_root()?,
+            self.b.hash_tree_root()?
+        ])
+    }
+
+    fn field_sizes() -> Vec<Option<usize>> {
+        alloc::vec![u32::fixed_size(), u8::fixed_size()]
+    }
+
+    fn from_fields(fields: &[&[u8]]) -> Result<Self, SSZError> {
+        Ok(Foo {
+            a: u32::deserialize(fields[0])?,
+            b: u8::deserialize(fields[1])?,
+        })
+    }
+}
+
+/// `Foo` is fixed-size with no byte-list fields, so its view is `Foo` itself.
+impl SimpleDeserializeRef<'_> for Foo {
+    fn deserialize_ref(data: &[u8]) -> Result<Self, SSZError> {
+        Self::deserialize(data)
     }
 }
 
 /// `SszTypeInfo` for `Foo`.
 impl crate::ssz::SszTypeInfo for Foo {
-    fn is_fixed_size() -> bool {
-        true
-    }
+    const IS_FIXED: bool = true;
 
     fn fixed_size() -> Option<usize> {
         Some(5)
@@ -50,65 +367,157 @@ impl crate::ssz::SszTypeInfo for Foo {
     }
 }
 
-/// Merkleization of `Foo`.
-impl crate::ssz::Merkleize for Foo {
-    fn hash_tree_root(&self) -> Result<alloy_primitives::B256, SSZError> {
-        let a_root = self.a.hash_tree_root()?;
-        let b_root = self.b.hash_tree_root()?;
-        crate::merkleization::merkleize(&[*a_root, *b_root], None)
+/// Field paths of `Foo`.
+impl FieldPath for Foo {
+    fn generalized_index(path: &[&str]) -> Result<u64, SSZError> {
+        container_generalized_index(&["a", "b"], path, |position, rest| match position {
+            0 => u32::generalized_index(rest),
+            _ => u8::generalized_index(rest),
+        })
     }
-    fn chunk_count() -> usize {
-        1
+}
+
+/// Converts `Foo` to and from a `serde_json::Value`.
+#[cfg(feature = "reflect")]
+impl crate::reflect::SszReflect for Foo {
+    fn field_names() -> &'static [&'static str] {
+        &["a", "b"]
+    }
+
+    fn to_obj(&self) -> serde_json::Value {
+        serde_json::json!({
+            "a": self.a.to_obj(),
+            "b": self.b.to_obj(),
+        })
+    }
+
+    fn from_obj(value: &serde_json::Value) -> Result<Self, SSZError> {
+        Ok(Foo {
+            a: crate::reflect::reflect_field(value, "a")?,
+            b: crate::reflect::reflect_field(value, "b")?,
+        })
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Default,
+    crate::SimpleSerialize,
+    crate::SimpleDeserialize,
+    crate::SszTypeInfo,
+    crate::Merkleize,
+)]
 pub struct TestComposite {
     pub name: bool,
     pub value: u32,
 }
 
-impl SimpleSerialize for TestComposite {
-    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-        let mut written = 0;
-        written += self.name.serialize(buffer)?;
-        written += self.value.serialize(buffer)?;
-        Ok(written)
+impl TestComposite {
+    /// Returns the indices of the fields whose `hash_tree_root` differs from `other`'s.
+    pub fn field_diff(&self, other: &Self) -> Result<Vec<usize>, SSZError> {
+        Ok(diff_field_roots(
+            &[self.name.hash_tree_root()?, self.value.hash_tree_root()?],
+            &[other.name.hash_tree_root()?, other.value.hash_tree_root()?],
+        ))
     }
 }
 
-impl SimpleDeserialize for TestComposite {
-    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        let (name, rest) = {
-            let name = bool::deserialize(&data[0..1])?;
-            (name, &data[1..])
-        };
-        let value = u32::deserialize(rest)?;
-        Ok(TestComposite { name, value })
+/// Container with variable-size byte-list fields, for testing borrowed decoding.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Envelope {
+    pub version: u16,
+    pub payload: Vec<u8>,
+    pub extra: Vec<u8>,
+}
+
+/// Borrowed view of an encoded `Envelope`; the byte-list fields point into the input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvelopeView<'a> {
+    pub version: u16,
+    pub payload: &'a [u8],
+    pub extra: &'a [u8],
+}
+
+impl EnvelopeView<'_> {
+    /// Copies the borrowed fields into an owned `Envelope`.
+    pub fn to_envelope(&self) -> Envelope {
+        Envelope {
+            version: self.version,
+            payload: self.payload.to_vec(),
+            extra: self.extra.to_vec(),
+        }
     }
 }
 
-impl crate::ssz::SszTypeInfo for TestComposite {
-    fn is_fixed_size() -> bool {
-        true
+impl SimpleSerialize for Envelope {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        serialize_fields(
+            [
+                (&self.version as &dyn SimpleSerialize, true),
+                (&self.payload as &dyn SimpleSerialize, false),
+                (&self.extra as &dyn SimpleSerialize, false),
+            ],
+            buffer,
+        )
     }
+}
 
-    fn fixed_size() -> Option<usize> {
-        Some(5)
+/// Decodes the fixed section and offsets, borrowing both byte lists from `data`.
+impl<'a> SimpleDeserializeRef<'a> for EnvelopeView<'a> {
+    fn deserialize_ref(data: &'a [u8]) -> Result<Self, SSZError> {
+        const FIXED_LEN: usize = 2 + 2 * BYTES_PER_LENGTH_OFFSET;
+        if data.len() < FIXED_LEN {
+            return Err(SSZError::ExpectedFurtherInput);
+        }
+
+        let version = u16::deserialize(&data[0..2])?;
+        let payload_offset = u32::deserialize(&data[2..6])? as usize;
+        let extra_offset = u32::deserialize(&data[6..10])? as usize;
+        if payload_offset != FIXED_LEN || extra_offset > data.len() {
+            return Err(SSZError::OffsetOutOfBounds);
+        }
+        if payload_offset > extra_offset {
+            return Err(SSZError::InvalidOffsetRange {
+                start: payload_offset,
+                end: extra_offset,
+            });
+        }
+
+        Ok(Self {
+            version,
+            payload: &data[payload_offset..extra_offset],
+            extra: &data[extra_offset..],
+        })
     }
+}
 
-    fn is_basic_type() -> bool {
-        false
+impl SimpleDeserialize for Envelope {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        Ok(EnvelopeView::deserialize_ref(data)?.to_envelope())
     }
 }
-impl crate::ssz::Merkleize for TestComposite {
-    fn hash_tree_root(&self) -> Result<alloy_primitives::B256, SSZError> {
-        let name_root = self.name.hash_tree_root()?;
-        let value_root = self.value.hash_tree_root()?;
-        crate::merkleization::merkleize(&[*name_root, *value_root], None)
+
+impl crate::ssz::SszTypeInfo for Envelope {
+    const IS_FIXED: bool = false;
+
+    fn fixed_size() -> Option<usize> {
+        None
     }
+}
+
+impl crate::ssz::Merkleize for Envelope {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        merkleize_fields(alloc::vec![
+            self.version.hash_tree_root()?,
+            self.payload.hash_tree_root()?,
+            self.extra.hash_tree_root()?,
+        ])
+    }
+
     fn chunk_count() -> usize {
-        1
+        3
     }
 }
 
@@ -119,7 +528,9 @@ mod test {
     use crate::container::TestComposite;
     use crate::ssz::Merkleize;
     use crate::ssz::SimpleSerialize;
+    use crate::ssz::assert_fixed_size_correct;
     use alloc::vec;
+    use alloc::vec::Vec;
     use alloy_primitives::B256;
     use alloy_primitives::hex;
 
@@ -135,6 +546,129 @@ mod test {
         assert_eq!(original.b, deserialized.b);
     }
 
+    #[test]
+    pub fn test_vec_of_fixed_containers_bulk_decode() {
+        use alloc::vec::Vec;
+
+        let foos: Vec<Foo> = (0..1000u32)
+            .map(|i| Foo {
+                a: i * 7,
+                b: i as u8,
+            })
+            .collect();
+        let mut buffer = vec![];
+        foos.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(buffer.len(), 5000);
+
+        let bulk = Vec::<Foo>::deserialize(&buffer).expect("can deserialize");
+        let generic: Vec<Foo> = buffer
+            .chunks(5)
+            .map(|chunk| Foo::deserialize(chunk).expect("can deserialize"))
+            .collect();
+        assert_eq!(bulk, generic);
+        assert_eq!(bulk, foos);
+
+        assert!(Vec::<Foo>::deserialize(&buffer[..4999]).is_err());
+    }
+
+    #[test]
+    pub fn test_serialize_fields_mixed_layout() {
+        use super::serialize_fields;
+        use alloc::vec::Vec;
+
+        let a = 5u16;
+        let b: Vec<u8> = vec![1, 2, 3];
+        let c = 9u8;
+        let d: Vec<u8> = vec![];
+        let fields: [(&dyn SimpleSerialize, bool); 4] =
+            [(&a, true), (&b, false), (&c, true), (&d, false)];
+
+        let mut buffer = vec![];
+        let written = serialize_fields(fields, &mut buffer).expect("can serialize fields");
+        let expected = vec![5, 0, 11, 0, 0, 0, 9, 14, 0, 0, 0, 1, 2, 3];
+        assert_eq!(buffer, expected);
+        assert_eq!(written, expected.len());
+    }
+
+    #[test]
+    pub fn test_serialize_fields_lengths_stable_between_passes() {
+        use super::serialize_fields;
+        use alloc::vec::Vec;
+
+        // Nested variable-size fields exercise the debug assertions on both passes.
+        let a: Vec<Vec<u8>> = vec![vec![1], vec![], vec![2, 3]];
+        let b = 7u32;
+        let c: Vec<u16> = vec![4, 5];
+        let fields: [(&dyn SimpleSerialize, bool); 3] = [(&a, false), (&b, true), (&c, false)];
+
+        let mut buffer = vec![0xaa];
+        let written = serialize_fields(fields, &mut buffer).expect("can serialize fields");
+        assert_eq!(
+            written,
+            a.ssz_bytes_len().unwrap()
+                + b.ssz_bytes_len().unwrap()
+                + c.ssz_bytes_len().unwrap()
+                + 8
+        );
+        assert_eq!(buffer.len(), written + 1);
+    }
+
+    #[test]
+    pub fn test_serialize_fields_propagates_length_errors() {
+        use super::serialize_fields;
+        use crate::SSZError;
+
+        struct Unencodable;
+        impl SimpleSerialize for Unencodable {
+            fn serialize(&self, _buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+                Err(SSZError::InvalidByte)
+            }
+        }
+
+        assert_eq!(Unencodable.ssz_bytes_len(), Err(SSZError::InvalidByte));
+
+        // The offsets can't be computed, so nothing is written.
+        let fields: [(&dyn SimpleSerialize, bool); 2] = [(&7u32, true), (&Unencodable, false)];
+        let mut buffer = vec![0xaa];
+        assert_eq!(
+            serialize_fields(fields, &mut buffer),
+            Err(SSZError::InvalidByte)
+        );
+        assert_eq!(buffer, [0xaa]);
+
+        #[cfg(feature = "std")]
+        {
+            let mut writer = Vec::new();
+            assert!(super::write_fields(fields, &mut writer).is_err());
+            assert!(writer.is_empty());
+        }
+    }
+
+    #[test]
+    pub fn test_container_field_diff() {
+        let original = Foo { a: 12, b: 6 };
+        assert_eq!(
+            original.field_diff(&original.clone()).unwrap(),
+            Vec::<usize>::new()
+        );
+        assert_eq!(original.field_diff(&Foo { a: 13, b: 6 }).unwrap(), vec![0]);
+        assert_eq!(original.field_diff(&Foo { a: 12, b: 7 }).unwrap(), vec![1]);
+        assert_eq!(
+            original.field_diff(&Foo { a: 0, b: 0 }).unwrap(),
+            vec![0, 1]
+        );
+
+        let composite = TestComposite {
+            name: true,
+            value: 1,
+        };
+        let changed = TestComposite {
+            name: true,
+            value: 2,
+        };
+        assert_eq!(composite.field_diff(&changed).unwrap(), vec![1]);
+    }
+
     #[test]
     pub fn test_container_merkleize() {
         let original = super::Foo { a: 12, b: 6 };
@@ -145,6 +679,23 @@ mod test {
         assert_eq!(root, expected_root);
     }
 
+    #[cfg(feature = "reflect")]
+    #[test]
+    pub fn test_container_reflect_roundtrip() {
+        use crate::reflect::SszReflect;
+
+        let original = Foo { a: 12, b: 6 };
+        let value = original.to_obj();
+        assert_eq!(value, serde_json::json!({ "a": 12, "b": 6 }));
+        assert_eq!(Foo::field_names(), &["a", "b"]);
+
+        let recovered = Foo::from_obj(&value).expect("can parse Foo");
+        assert_eq!(recovered, original);
+
+        let missing = serde_json::json!({ "a": 12 });
+        assert!(Foo::from_obj(&missing).is_err());
+    }
+
     #[test]
     pub fn test_composite_serialization() {
         use crate::ssz::Merkleize;
@@ -180,4 +731,321 @@ mod test {
         assert_eq!(root, expected_a_root);
         assert_eq!(op_root, expected_opa_root);
     }
+
+    #[test]
+    fn test_fixed_size_matches_serialization() {
+        assert_fixed_size_correct::<Foo>();
+        assert_fixed_size_correct::<TestComposite>();
+    }
+
+    #[test]
+    fn test_nested_field_generalized_index() {
+        use crate::field_path::{FieldPath, container_generalized_index};
+        use crate::merkleization::{MerkleProof, merkleize_fields};
+
+        struct Outer;
+
+        impl FieldPath for Outer {
+            fn generalized_index(path: &[&str]) -> Result<u64, crate::SSZError> {
+                container_generalized_index(
+                    &["x", "inner"],
+                    path,
+                    |position, rest| match position {
+                        0 => u64::generalized_index(rest),
+                        _ => Foo::generalized_index(rest),
+                    },
+                )
+            }
+        }
+
+        assert_eq!(Foo::generalized_index(&["b"]), Ok(3));
+        assert_eq!(Outer::generalized_index(&["inner"]), Ok(3));
+        assert_eq!(Outer::generalized_index(&["inner", "b"]), Ok(7));
+        assert_eq!(Outer::generalized_index(&["inner", "a"]), Ok(6));
+        assert_eq!(Outer::generalized_index(&[]), Ok(1));
+        assert!(Outer::generalized_index(&["inner", "c"]).is_err());
+        assert!(Outer::generalized_index(&["x", "a"]).is_err());
+
+        // The resolved index addresses `inner.b` in the actual tree.
+        let x = 9u64;
+        let inner = Foo { a: 1, b: 2 };
+        let root = merkleize_fields(vec![
+            x.hash_tree_root().unwrap(),
+            inner.hash_tree_root().unwrap(),
+        ])
+        .unwrap();
+        let proof = MerkleProof {
+            index: Outer::generalized_index(&["inner", "b"]).unwrap(),
+            leaf: inner.b.hash_tree_root().unwrap(),
+            branch: vec![
+                inner.a.hash_tree_root().unwrap(),
+                x.hash_tree_root().unwrap(),
+            ],
+        };
+        assert_eq!(proof.root(), Ok(root));
+    }
+
+    #[test]
+    fn test_envelope_view_borrows_byte_lists() {
+        use super::{Envelope, EnvelopeView};
+        use crate::SimpleDeserializeRef;
+
+        let envelope = Envelope {
+            version: 3,
+            payload: vec![1, 2, 3],
+            extra: vec![4],
+        };
+        let mut buffer = vec![];
+        envelope.serialize(&mut buffer).expect("can serialize");
+
+        let view = EnvelopeView::deserialize_ref(&buffer).expect("can deserialize");
+        assert_eq!(view.version, 3);
+        assert_eq!(view.payload, &[1, 2, 3]);
+        assert_eq!(view.payload.as_ptr(), buffer[10..].as_ptr());
+        assert_eq!(view.extra.as_ptr(), buffer[13..].as_ptr());
+        assert_eq!(view.to_envelope(), envelope);
+        assert_eq!(Envelope::deserialize(&buffer), Ok(envelope));
+
+        // The fixed `Foo` has nothing to borrow and decodes as itself.
+        assert_eq!(
+            Foo::deserialize_ref(&[1, 0, 0, 0, 2]),
+            Ok(Foo { a: 1, b: 2 })
+        );
+
+        let mut swapped = buffer.clone();
+        swapped[6..10].copy_from_slice(&9u32.to_le_bytes());
+        assert_eq!(
+            EnvelopeView::deserialize_ref(&swapped),
+            Err(crate::SSZError::InvalidOffsetRange { start: 10, end: 9 })
+        );
+    }
+
+    #[test]
+    fn test_derive_simple_serialize() {
+        use crate::ssz::SszTypeInfo;
+        use crate::ssz_list::List;
+
+        #[derive(crate::SimpleSerialize, crate::SszTypeInfo)]
+        struct Mixed {
+            data: Vec<u8>,
+            slot: u64,
+            values: List<u16, 8>,
+        }
+
+        let mixed = Mixed {
+            data: vec![1, 2, 3],
+            slot: 5,
+            values: List::new(vec![7, 8]).unwrap(),
+        };
+        let mut buffer = vec![0xaa];
+        let written = mixed.serialize(&mut buffer).expect("can serialize");
+        let expected = [
+            16, 0, 0, 0, // offset of `data`
+            5, 0, 0, 0, 0, 0, 0, 0, // `slot`
+            19, 0, 0, 0, // offset of `values`
+            1, 2, 3, // `data`
+            7, 0, 8, 0, // `values`
+        ];
+        assert_eq!(buffer[1..], expected);
+        assert_eq!(written, expected.len());
+        assert_eq!(mixed.ssz_bytes_len(), Ok(expected.len()));
+        const { assert!(!Mixed::IS_FIXED) };
+        assert_eq!(Mixed::fixed_size(), None);
+
+        // Generic containers bound each field type by the derived traits.
+        #[derive(Default, crate::SimpleSerialize, crate::SszTypeInfo)]
+        struct Pair<T> {
+            left: T,
+            right: T,
+        }
+        const { assert!(Pair::<u32>::IS_FIXED) };
+        assert_fixed_size_correct::<Pair<u32>>();
+
+        let pair = Pair {
+            left: vec![1u8],
+            right: vec![2, 3],
+        };
+        let mut buffer = vec![];
+        pair.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(buffer, [8, 0, 0, 0, 9, 0, 0, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_derive_simple_deserialize() {
+        use crate::SSZError;
+
+        #[derive(
+            Debug, PartialEq, crate::SimpleSerialize, crate::SimpleDeserialize, crate::SszTypeInfo,
+        )]
+        struct Mixed {
+            a: u16,
+            data: Vec<u8>,
+            b: bool,
+            more: Vec<u32>,
+        }
+
+        let mixed = Mixed {
+            a: 0x0102,
+            data: vec![1, 2, 3],
+            b: true,
+            more: vec![4, 5],
+        };
+        let mut buffer = vec![];
+        mixed.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(Mixed::deserialize(&buffer), Ok(mixed));
+
+        // `data` starts at 11 and `more` at 14; pointing `more` before `data` is rejected.
+        let mut decreasing = buffer.clone();
+        decreasing[7..11].copy_from_slice(&10u32.to_le_bytes());
+        assert_eq!(
+            Mixed::deserialize(&decreasing),
+            Err(SSZError::InvalidOffsetRange { start: 11, end: 10 })
+        );
+
+        let mut bad_first = buffer.clone();
+        bad_first[2..6].copy_from_slice(&12u32.to_le_bytes());
+        assert_eq!(
+            Mixed::deserialize(&bad_first),
+            Err(SSZError::OffsetOutOfBounds)
+        );
+
+        #[derive(Debug, PartialEq, crate::SimpleDeserialize, crate::SszTypeInfo)]
+        struct Fixed {
+            a: u32,
+            b: bool,
+        }
+        assert_eq!(
+            Fixed::deserialize(&[1, 0, 0, 0, 1]),
+            Ok(Fixed { a: 1, b: true })
+        );
+        assert_eq!(
+            Fixed::deserialize(&[1, 0, 0, 0, 1, 0]),
+            Err(SSZError::InvalidByteLength {
+                expected: 5,
+                got: 6
+            })
+        );
+        assert_eq!(
+            Fixed::deserialize(&[1, 0, 0, 0]),
+            Err(SSZError::ExpectedFurtherInput)
+        );
+    }
+
+    #[test]
+    fn test_derive_merkleize() {
+        use crate::merkleization::merkleize;
+
+        #[derive(crate::Merkleize)]
+        struct Triple {
+            a: u32,
+            b: Vec<u8>,
+            c: bool,
+        }
+
+        let triple = Triple {
+            a: 7,
+            b: vec![1, 2],
+            c: true,
+        };
+        // Three field roots pad to four chunks.
+        let manual = merkleize(
+            &[
+                triple.a.hash_tree_root().unwrap().0,
+                triple.b.hash_tree_root().unwrap().0,
+                triple.c.hash_tree_root().unwrap().0,
+                [0u8; 32],
+            ],
+            None,
+        )
+        .unwrap();
+        assert_eq!(triple.hash_tree_root().unwrap(), manual);
+        assert_eq!(Triple::chunk_count(), 3);
+        assert_eq!(Triple::merkle_depth(), 2);
+    }
+
+    #[test]
+    fn test_foo_through_ssz_container() {
+        use super::SszContainer;
+        use crate::SSZError;
+
+        let foo = Foo {
+            a: 0x0102_0304,
+            b: 5,
+        };
+        let mut buffer = vec![];
+        foo.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(buffer, vec![4, 3, 2, 1, 5]);
+        assert_eq!(Foo::deserialize(&buffer), Ok(foo.clone()));
+        assert_eq!(Foo::FIELD_COUNT, 2);
+
+        assert_eq!(
+            Foo::deserialize(&buffer[..4]),
+            Err(SSZError::ExpectedFurtherInput)
+        );
+        assert!(Foo::deserialize(&[4, 3, 2, 1, 5, 0]).is_err());
+    }
+
+    #[test]
+    fn test_container_chunk_count_is_field_count() {
+        use crate::merkleization::{MerkleProof, get_generalized_index};
+
+        // Each field root is one chunk, so two fields make a tree of depth 1. The hand-written
+        // `chunk_count` of 1 claimed a single leaf, which placed `b` at the root.
+        assert_eq!(Foo::chunk_count(), 2);
+        assert_eq!(Foo::merkle_depth(), 1);
+        assert_eq!(TestComposite::chunk_count(), 2);
+
+        let foo = Foo { a: 12, b: 6 };
+        let proof = MerkleProof {
+            index: get_generalized_index(Foo::chunk_count(), 1),
+            leaf: foo.b.hash_tree_root().unwrap(),
+            branch: vec![foo.a.hash_tree_root().unwrap()],
+        };
+        assert_eq!(proof.branch.len(), Foo::merkle_depth() as usize);
+        assert_eq!(proof.root(), foo.hash_tree_root());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_serialize_to_writer_matches_buffer() {
+        use crate::ssz_list::List;
+        use alloc::vec::Vec;
+
+        #[derive(crate::SimpleSerialize, crate::SszTypeInfo, crate::Merkleize)]
+        struct Inner {
+            id: u16,
+            tags: List<u8, 4>,
+        }
+
+        #[derive(crate::SimpleSerialize, crate::SszTypeInfo)]
+        struct Outer {
+            slot: u64,
+            inners: List<Inner, 4>,
+            pair: (u32, Vec<u8>),
+            roots: Vec<[u8; 2]>,
+        }
+
+        let outer = Outer {
+            slot: 9,
+            inners: List::new(vec![
+                Inner {
+                    id: 1,
+                    tags: List::new(vec![1, 2]).unwrap(),
+                },
+                Inner {
+                    id: 2,
+                    tags: List::new(vec![]).unwrap(),
+                },
+            ])
+            .unwrap(),
+            pair: (3, vec![4, 5, 6]),
+            roots: vec![[7, 8], [9, 10]],
+        };
+
+        let mut buffer = vec![];
+        let written = outer.serialize(&mut buffer).expect("can serialize");
+        let mut streamed: Vec<u8> = vec![];
+        assert_eq!(outer.serialize_to_writer(&mut streamed).unwrap(), written);
+        assert_eq!(streamed, buffer);
+    }
 }
@@ -0,0 +1,85 @@
+//! Serialization , deserialization and merkleization for borrowed byte lists.
+
+use crate::{
+    Merkleize, SSZError, SimpleDeserialize, SimpleDeserializeRef, SimpleSerialize, SszTypeInfo,
+};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+
+/// SSZTypeInfo implementation for `Cow<[u8]>`
+impl SszTypeInfo for Cow<'_, [u8]> {
+    const IS_FIXED: bool = false;
+
+    fn fixed_size() -> Option<usize> {
+        None
+    }
+}
+
+/// Serializes `Cow<[u8]>` as raw bytes, same as `Vec<u8>`.
+impl SimpleSerialize for Cow<'_, [u8]> {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        buffer.extend_from_slice(self);
+        Ok(self.len())
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(self.len())
+    }
+}
+
+/// Borrows the input slice without copying it.
+impl<'a> SimpleDeserializeRef<'a> for Cow<'a, [u8]> {
+    fn deserialize_ref(data: &'a [u8]) -> Result<Self, SSZError> {
+        Ok(Cow::Borrowed(data))
+    }
+}
+
+/// Copies the input when no lifetime is available to borrow from.
+impl SimpleDeserialize for Cow<'_, [u8]> {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        Ok(Cow::Owned(data.to_vec()))
+    }
+}
+
+/// Merkleizes `Cow<[u8]>` exactly like the equivalent `Vec<u8>`.
+impl Merkleize for Cow<'_, [u8]> {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        self.to_vec().hash_tree_root()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_cow_borrows_top_level_byte_list() {
+        let data = vec![1u8, 2, 3, 4];
+        let cow = Cow::<[u8]>::deserialize_ref(&data).expect("can deserialize");
+        assert!(matches!(cow, Cow::Borrowed(_)));
+        assert_eq!(cow.as_ptr(), data.as_ptr());
+        assert_eq!(&*cow, &[1, 2, 3, 4]);
+
+        let owned = cow.into_owned();
+        assert_eq!(owned, data);
+    }
+
+    #[test]
+    fn test_cow_roundtrip_and_root() {
+        let cow: Cow<'_, [u8]> = Cow::Owned(vec![5u8, 6, 7]);
+        let mut buffer = vec![];
+        cow.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(buffer, vec![5, 6, 7]);
+
+        let decoded = Cow::<[u8]>::deserialize(&buffer).expect("can deserialize");
+        assert!(matches!(decoded, Cow::Owned(_)));
+        assert_eq!(decoded, cow);
+
+        assert_eq!(
+            cow.hash_tree_root().unwrap(),
+            vec![5u8, 6, 7].hash_tree_root().unwrap()
+        );
+    }
+}
@@ -1,14 +1,15 @@
 //! Serialization , deserialization and merkleization for FixedBytes.
 
-use crate::{Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo};
+use crate::{
+    BYTES_PER_CHUNK, Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
+    merkleization::{merkleize, pack, pad_to_chunk},
+};
 use alloc::vec::Vec;
-use alloy_primitives::{B256, FixedBytes};
+use alloy_primitives::{Address, B256, FixedBytes};
 
 /// SSZTypeInfo implementation for FixedBytes
 impl<const N: usize> SszTypeInfo for FixedBytes<N> {
-    fn is_fixed_size() -> bool {
-        true
-    }
+    const IS_FIXED: bool = true;
 
     fn fixed_size() -> Option<usize> {
         Some(N)
@@ -24,6 +25,10 @@ impl<const N: usize> SimpleSerialize for FixedBytes<N> {
         buffer.extend_from_slice(&self.0);
         Ok(N)
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(N)
+    }
 }
 
 impl<const N: usize> SimpleDeserialize for FixedBytes<N> {
@@ -39,12 +44,120 @@ impl<const N: usize> SimpleDeserialize for FixedBytes<N> {
     }
 }
 
+/// Merkleizes `FixedBytes<N>` as an SSZ `Vector[byte, N]`: the bytes are packed into chunks
+/// and merkleized without a length mix-in.
 impl<const N: usize> Merkleize for FixedBytes<N> {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        self.0.hash_tree_root()
+        merkleize(&pack(&self.0), Some(Self::chunk_count()))
     }
 
     fn chunk_count() -> usize {
-        <[u8; N] as Merkleize>::chunk_count()
+        N.div_ceil(BYTES_PER_CHUNK)
+    }
+}
+
+/// SSZTypeInfo implementation for Address
+///
+/// An address is a `Vector[byte, 20]`. It is not packed like a basic type, so a list of
+/// addresses merkleizes one chunk per address rather than packing them across chunks.
+impl SszTypeInfo for Address {
+    const IS_FIXED: bool = true;
+
+    fn fixed_size() -> Option<usize> {
+        Some(20)
+    }
+}
+
+impl SimpleSerialize for Address {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        self.0.serialize(buffer)
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(20)
+    }
+}
+
+impl SimpleDeserialize for Address {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        FixedBytes::<20>::deserialize(data).map(Address)
+    }
+}
+
+/// Merkleizes an address as its 20 bytes right-padded into a single chunk.
+impl Merkleize for Address {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        pad_to_chunk(self.as_slice()).map(B256::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex};
+
+    #[test]
+    fn test_address_roundtrip_and_root() {
+        let addr = address!("d8da6bf26964af9d7eed9e03e53415d37aa96045");
+        let mut buffer = Vec::new();
+        assert_eq!(addr.serialize(&mut buffer), Ok(20));
+        assert_eq!(buffer, addr.as_slice());
+        assert_eq!(Address::deserialize(&buffer), Ok(addr));
+
+        assert_eq!(
+            addr.hash_tree_root().unwrap(),
+            B256::from(hex!(
+                "d8da6bf26964af9d7eed9e03e53415d37aa96045000000000000000000000000"
+            ))
+        );
+        crate::ssz::assert_fixed_size_correct::<Address>();
+    }
+
+    fn bytes<const N: usize>() -> FixedBytes<N> {
+        FixedBytes(core::array::from_fn(|i| i as u8))
+    }
+
+    #[test]
+    fn test_fixed_bytes_root_is_vector_root() {
+        assert_eq!(
+            bytes::<32>().hash_tree_root().unwrap(),
+            B256::from(hex!(
+                "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+            ))
+        );
+        assert_eq!(
+            bytes::<48>().hash_tree_root().unwrap(),
+            B256::from(hex!(
+                "b976c9abe97b4f03d7e4058246713687379d2718a829ab66e2a93aa924e43c1d"
+            ))
+        );
+        assert_eq!(
+            bytes::<96>().hash_tree_root().unwrap(),
+            B256::from(hex!(
+                "17c8d5caa3d7162e8dada90de6e741783f3d73736498c11eba34974fbf5464f3"
+            ))
+        );
+
+        let b256 = B256::repeat_byte(0xab);
+        assert_eq!(b256.hash_tree_root().unwrap(), b256);
+        assert_eq!(FixedBytes::<96>::chunk_count(), 3);
+    }
+
+    #[test]
+    fn test_address_rejects_wrong_length() {
+        assert_eq!(
+            Address::deserialize(&[0u8; 19]),
+            Err(SSZError::InvalidLength {
+                expected: 20,
+                got: 19
+            })
+        );
+        assert_eq!(
+            Address::deserialize(&[0u8; 32]),
+            Err(SSZError::InvalidLength {
+                expected: 20,
+                got: 32
+            })
+        );
     }
 }
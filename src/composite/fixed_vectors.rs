@@ -2,20 +2,66 @@
 
 use crate::{
     Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
-    merkleization::{SSZType, chunk_count, merkleize, pack},
+    list::try_array_from_fn,
+    merkleization::{
+        BYTES_PER_CHUNK, CachedTree, MerkleProof, SSZType, chunk_count, merkleize, pack,
+    },
+    ssz_list::List,
 };
-use alloc::{vec, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use alloy_primitives::B256;
-use core::mem::MaybeUninit;
 use core::ops::{Deref, DerefMut};
 
+/// SSZ `Vector[T, N]` holding exactly `N` elements.
+///
+/// The SSZ spec forbids empty vectors, so `FixedVector<T, 0>` fails to compile once it is
+/// constructed or deserialized.
+///
+/// ```compile_fail
+/// use r_ssz::fixed_vectors::FixedVector;
+/// let _ = FixedVector::<u8, 0>::new([]);
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FixedVector<T, const N: usize>([T; N]);
 
 impl<T, const N: usize> FixedVector<T, N> {
     pub fn new(data: [T; N]) -> Self {
+        const { assert!(N > 0, "FixedVector length must be non-zero") };
         Self(data)
     }
+
+    /// Builds a vector from `elements`; fails unless there are exactly `N`.
+    pub fn try_from_vec(elements: Vec<T>) -> Result<Self, SSZError> {
+        let len = elements.len();
+        let elements: [T; N] = elements.try_into().map_err(|_| SSZError::InvalidLength {
+            expected: N,
+            got: len,
+        })?;
+        Ok(Self::new(elements))
+    }
+}
+
+/// SSZ `Vector[byte, N]` helpers.
+impl<const N: usize> FixedVector<u8, N> {
+    /// Decodes a byte vector from a hex string, with or without a `0x` prefix; fails unless it
+    /// holds exactly `N` bytes.
+    pub fn from_hex(s: &str) -> Result<Self, SSZError> {
+        crate::hex_util::from_hex(s)
+    }
+
+    /// Encodes the bytes as a `0x`-prefixed hex string.
+    pub fn to_hex(&self) -> Result<String, SSZError> {
+        crate::hex_util::to_hex(self)
+    }
+}
+
+/// Copies a slice into a vector; fails unless it holds exactly `N` elements.
+impl<T: Clone, const N: usize> TryFrom<&[T]> for FixedVector<T, N> {
+    type Error = SSZError;
+
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        Self::try_from_vec(slice.to_vec())
+    }
 }
 
 impl<T, const N: usize> Deref for FixedVector<T, N> {
@@ -31,14 +77,57 @@ impl<T, const N: usize> DerefMut for FixedVector<T, N> {
     }
 }
 
+/// Builds fixed vectors from homogeneous tuples, e.g. `FixedVector::from((1u16, 2, 3))`.
+macro_rules! impl_from_tuple {
+    (@ty $elem:ident) => {
+        T
+    };
+    ($n:literal; $($elem:ident),+) => {
+        impl<T> From<($(impl_from_tuple!(@ty $elem),)+)> for FixedVector<T, $n> {
+            fn from(($($elem,)+): ($(impl_from_tuple!(@ty $elem),)+)) -> Self {
+                Self::new([$($elem),+])
+            }
+        }
+    };
+}
+
+impl_from_tuple!(1; a);
+impl_from_tuple!(2; a, b);
+impl_from_tuple!(3; a, b, c);
+impl_from_tuple!(4; a, b, c, d);
+impl_from_tuple!(5; a, b, c, d, e);
+impl_from_tuple!(6; a, b, c, d, e, f);
+impl_from_tuple!(7; a, b, c, d, e, f, g);
+impl_from_tuple!(8; a, b, c, d, e, f, g, h);
+
+/// Converts a full list into a vector; fails unless the list holds exactly `N` elements.
+///
+/// The conversion changes merkleization semantics: a `FixedVector` root does not mix in the
+/// length, so it differs from the root of the `List` it was built from.
+impl<T, const N: usize> TryFrom<List<T, N>> for FixedVector<T, N> {
+    type Error = SSZError;
+
+    fn try_from(list: List<T, N>) -> Result<Self, Self::Error> {
+        Self::try_from_vec(list.into_inner())
+    }
+}
+
+/// Converts a vector into a full list.
+///
+/// The conversion changes merkleization semantics: the resulting `List` root mixes in the
+/// length `N`, so it differs from the root of the `FixedVector` it was built from.
+impl<T, const N: usize> From<FixedVector<T, N>> for List<T, N> {
+    fn from(vector: FixedVector<T, N>) -> Self {
+        List::new(Vec::from(vector.0)).expect("a vector of N elements fits in List<T, N>")
+    }
+}
+
 /// Provides `SszTypeInfo` for fixed vector.
 impl<T, const N: usize> SszTypeInfo for FixedVector<T, N>
 where
     T: SszTypeInfo,
 {
-    fn is_fixed_size() -> bool {
-        T::is_fixed_size()
-    }
+    const IS_FIXED: bool = T::IS_FIXED;
 
     fn fixed_size() -> Option<usize> {
         if T::is_fixed_size() {
@@ -87,6 +176,17 @@ where
 
         Ok(buffer.len() - start_len)
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        match T::fixed_size() {
+            Some(size) if T::is_fixed_size() => Ok(N * size),
+            _ => self
+                .0
+                .iter()
+                .map(|item| Ok(crate::BYTES_PER_LENGTH_OFFSET + item.ssz_bytes_len()?))
+                .sum(),
+        }
+    }
 }
 
 /// Implements deserialization of fixed vector.
@@ -95,6 +195,7 @@ where
     T: SimpleDeserialize + SszTypeInfo,
 {
     fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        const { assert!(N > 0, "FixedVector length must be non-zero") };
         if T::is_fixed_size() {
             let elem_size = T::fixed_size().ok_or(SSZError::InvalidByte)?;
             if data.len() != elem_size * N {
@@ -104,15 +205,8 @@ where
                 });
             }
 
-            let mut array: [MaybeUninit<T>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-            for (i, slot) in array.iter_mut().enumerate() {
-                let start = i * elem_size;
-                let end = start + elem_size;
-                *slot = MaybeUninit::new(T::deserialize(&data[start..end])?);
-            }
-
-            let initialized = unsafe { core::mem::transmute_copy::<_, [T; N]>(&array) };
-            Ok(FixedVector(initialized))
+            try_array_from_fn(|i| T::deserialize(&data[i * elem_size..(i + 1) * elem_size]))
+                .map(FixedVector)
         } else {
             let offset_size = crate::BYTES_PER_LENGTH_OFFSET;
             let expected_offsets = N * offset_size;
@@ -130,49 +224,86 @@ where
                 *item = u32::from_le_bytes(data[start..end].try_into().unwrap()) as usize;
             }
 
-            let mut array: [MaybeUninit<T>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-            for i in 0..N {
+            // The first offset must point just past the offset table, and each element slice
+            // must lie within the data and start no later than it ends.
+            if offsets[0] != expected_offsets {
+                return Err(SSZError::OffsetOutOfBounds);
+            }
+            let mut ranges = [(0usize, 0usize); N];
+            for (i, range) in ranges.iter_mut().enumerate() {
                 let start = offsets[i];
                 let end = if i + 1 < N {
                     offsets[i + 1]
                 } else {
                     data.len()
                 };
-                array[i] = MaybeUninit::new(T::deserialize(&data[start..end])?);
+                if end > data.len() {
+                    return Err(SSZError::OffsetOutOfBounds);
+                }
+                if start > end {
+                    return Err(SSZError::InvalidOffsetRange { start, end });
+                }
+                *range = (start, end);
             }
 
-            let initialized = unsafe { core::mem::transmute_copy::<_, [T; N]>(&array) };
-            Ok(FixedVector(initialized))
+            try_array_from_fn(|i| {
+                let (start, end) = ranges[i];
+                T::deserialize(&data[start..end])
+            })
+            .map(FixedVector)
         }
     }
 }
 
-/// Implements Merkleization of fixed vector.
-impl<T, const N: usize> Merkleize for FixedVector<T, N>
+impl<T, const N: usize> FixedVector<T, N>
 where
     T: Merkleize + SimpleSerialize + SszTypeInfo,
 {
-    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+    /// Returns the leaf chunks of the vector's Merkle tree and the chunk count it pads to.
+    fn chunks(&self) -> Result<(Vec<[u8; 32]>, usize), SSZError> {
         if T::is_basic_type() {
             let mut serialized = vec![];
             self.serialize(&mut serialized)?;
-            let packed = pack(&serialized);
             let count = chunk_count(SSZType::VectorBasic {
                 elem_size: T::fixed_size().unwrap(),
                 count: N,
             });
-            merkleize(&packed, Some(count))
+            Ok((pack(&serialized), count))
         } else {
-            let roots: Result<Vec<[u8; 32]>, SSZError> = self
+            let roots = self
                 .0
                 .iter()
                 .map(|elem| elem.hash_tree_root().map(|b| b.0))
-                .collect();
-            merkleize(
-                &roots?,
-                Some(chunk_count(SSZType::VectorComposite { count: N })),
-            )
+                .collect::<Result<_, _>>()?;
+            Ok((roots, chunk_count(SSZType::VectorComposite { count: N })))
+        }
+    }
+
+    /// Proves the element at `index` against `hash_tree_root`.
+    ///
+    /// The leaf is the element's root, or for basic element types the packed chunk holding the
+    /// element.
+    pub fn prove(&self, index: usize) -> Result<MerkleProof, SSZError> {
+        if index >= N {
+            return Err(SSZError::IndexOutOfBounds { index, len: N });
         }
+        let chunk_index = match T::is_basic_type() {
+            true => index * T::fixed_size().unwrap() / BYTES_PER_CHUNK,
+            false => index,
+        };
+        let (chunks, count) = self.chunks()?;
+        Ok(CachedTree::new(&chunks, Some(count)).prove(chunk_index))
+    }
+}
+
+/// Implements Merkleization of fixed vector.
+impl<T, const N: usize> Merkleize for FixedVector<T, N>
+where
+    T: Merkleize + SimpleSerialize + SszTypeInfo,
+{
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        let (chunks, count) = self.chunks()?;
+        merkleize(&chunks, Some(count))
     }
 }
 
@@ -183,6 +314,58 @@ mod fixed_vector_tests {
     use alloc::vec::Vec;
     use alloy_primitives::{B256, hex};
 
+    #[test]
+    fn test_byte_vector_hex() {
+        let bytes = FixedVector::<u8, 3>::from_hex("0a0b0c").expect("valid hex");
+        assert_eq!(bytes, FixedVector::new([0x0a, 0x0b, 0x0c]));
+        assert_eq!(bytes.to_hex().unwrap(), "0x0a0b0c");
+        assert_eq!(
+            FixedVector::<u8, 3>::from_hex(&bytes.to_hex().unwrap()),
+            Ok(bytes)
+        );
+
+        assert!(FixedVector::<u8, 3>::from_hex("0x0a0b").is_err());
+        assert!(FixedVector::<u8, 3>::from_hex("0x0a0b0c0d").is_err());
+    }
+
+    #[test]
+    fn test_fixed_vector_try_from_vec() {
+        assert_eq!(
+            FixedVector::<u16, 3>::try_from_vec(vec![1, 2, 3]),
+            Ok(FixedVector::new([1, 2, 3]))
+        );
+        assert_eq!(
+            FixedVector::<u16, 3>::try_from(&[1u16, 2, 3][..]),
+            Ok(FixedVector::new([1, 2, 3]))
+        );
+
+        for elements in [vec![1u16, 2], vec![1, 2, 3, 4]] {
+            let got = elements.len();
+            assert_eq!(
+                FixedVector::<u16, 3>::try_from(elements.as_slice()),
+                Err(SSZError::InvalidLength { expected: 3, got })
+            );
+            assert_eq!(
+                FixedVector::<u16, 3>::try_from_vec(elements),
+                Err(SSZError::InvalidLength { expected: 3, got })
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_drops_decoded_elements_on_error() {
+        use crate::ssz::Tracked;
+
+        let mut buffer = vec![];
+        [7u32, u32::MAX, 7].serialize(&mut buffer).unwrap();
+        assert_eq!(
+            FixedVector::<Tracked<0>, 3>::deserialize(&buffer).unwrap_err(),
+            SSZError::InvalidByte
+        );
+        // Only the first element was decoded, and it was dropped exactly once.
+        assert_eq!(Tracked::<0>::live(), 0);
+    }
+
     #[test]
     fn test_fixed_vector_fixed_type_serialization() {
         let fv = FixedVector::<u16, 4>::new([1, 2, 3, 4]);
@@ -215,6 +398,39 @@ mod fixed_vector_tests {
         assert_eq!(fv, deserialized);
     }
 
+    #[test]
+    fn test_fixed_vector_rejects_invalid_offsets() {
+        // Second offset points back before the first one.
+        let mut data = Vec::new();
+        data.extend_from_slice(&8u32.to_le_bytes());
+        data.extend_from_slice(&6u32.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(
+            FixedVector::<Vec<u8>, 2>::deserialize(&data),
+            Err(SSZError::InvalidOffsetRange { start: 8, end: 6 })
+        );
+
+        // First offset doesn't match the size of the offset table.
+        let mut data = Vec::new();
+        data.extend_from_slice(&9u32.to_le_bytes());
+        data.extend_from_slice(&10u32.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(
+            FixedVector::<Vec<u8>, 2>::deserialize(&data),
+            Err(SSZError::OffsetOutOfBounds)
+        );
+
+        // Second offset past the end of the data.
+        let mut data = Vec::new();
+        data.extend_from_slice(&8u32.to_le_bytes());
+        data.extend_from_slice(&20u32.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(
+            FixedVector::<Vec<u8>, 2>::deserialize(&data),
+            Err(SSZError::OffsetOutOfBounds)
+        );
+    }
+
     #[test]
     fn test_fixed_vector_hash_tree_root_basic() {
         let fv = FixedVector::<u8, 4>::new([1, 2, 3, 4]);
@@ -233,4 +449,55 @@ mod fixed_vector_tests {
         let result = FixedVector::<u16, 2>::deserialize(&invalid_data);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_fixed_vector_list_conversion() {
+        let list = List::<u64, 3>::new(vec![1, 2, 3]).unwrap();
+        let fv = FixedVector::<u64, 3>::try_from(list.clone()).expect("list is full");
+        assert_eq!(fv, FixedVector::new([1, 2, 3]));
+        assert_eq!(List::from(fv.clone()), list);
+
+        // Same elements, different roots: only the list mixes in its length.
+        assert_ne!(fv.hash_tree_root().unwrap(), list.hash_tree_root().unwrap());
+
+        let short = List::<u64, 3>::new(vec![1, 2]).unwrap();
+        assert_eq!(
+            FixedVector::<u64, 3>::try_from(short),
+            Err(SSZError::InvalidLength {
+                expected: 3,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_fixed_vector_from_tuple() {
+        let fv = FixedVector::<u16, 3>::from((1, 2, 3));
+        assert_eq!(fv, FixedVector::new([1, 2, 3]));
+
+        let fv: FixedVector<u8, 4> = (1, 2, 3, 4).into();
+        let mut buffer = vec![];
+        fv.serialize(&mut buffer).expect("serialize fixed vector");
+        assert_eq!(buffer, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_fixed_vector_prove() {
+        // 40 u16s pack into 3 chunks, padded to 4; element 17 sits in chunk 1.
+        let fv = FixedVector::<u16, 40>::new(core::array::from_fn(|i| i as u16));
+        let proof = fv.prove(17).unwrap();
+        assert_eq!(proof.index, 5);
+        assert_eq!(proof.root(), Ok(fv.hash_tree_root().unwrap()));
+
+        let nested = FixedVector::<Vec<u8>, 3>::new([vec![1], vec![], vec![2, 3]]);
+        let proof = nested.prove(2).unwrap();
+        assert_eq!(proof.index, 6);
+        assert_eq!(proof.leaf, nested[2].hash_tree_root().unwrap());
+        assert_eq!(proof.root(), Ok(nested.hash_tree_root().unwrap()));
+
+        assert_eq!(
+            nested.prove(3).unwrap_err(),
+            SSZError::IndexOutOfBounds { index: 3, len: 3 }
+        );
+    }
 }
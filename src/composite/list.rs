@@ -5,19 +5,19 @@ use crate::{
     SimpleSerialize, SszTypeInfo,
     merkleization::{merkleize, mix_in_length, pack},
 };
+use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
 use alloy_primitives::B256;
 use core::convert::TryInto;
+use core::mem::MaybeUninit;
 
 impl<T, const N: usize> SszTypeInfo for [T; N]
 where
     T: SszTypeInfo,
 {
-    /// Returns true if size is fixed else false.
-    fn is_fixed_size() -> bool {
-        T::is_fixed_size()
-    }
+    /// Fixed size whenever the element type is.
+    const IS_FIXED: bool = T::IS_FIXED;
 
     /// Provides size info.
     fn fixed_size() -> Option<usize> {
@@ -62,84 +62,182 @@ where
 
         Ok(buffer.len() - start_len)
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        match T::fixed_size() {
+            Some(size) if T::is_fixed_size() => Ok(N * size),
+            _ => self
+                .iter()
+                .map(|item| Ok(BYTES_PER_LENGTH_OFFSET + item.ssz_bytes_len()?))
+                .sum(),
+        }
+    }
 }
 
-/// Implements deserialization for list.
-impl<T, const N: usize> SimpleDeserialize for [T; N]
+/// Checks that `data` can hold the `N` elements of a fixed-length sequence.
+fn check_elements_len<T, const N: usize>(data: &[u8]) -> Result<(), SSZError>
 where
-    T: SimpleDeserialize + Clone + SszTypeInfo,
+    T: SszTypeInfo,
 {
-    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if T::is_fixed_size() {
-            let size = T::fixed_size().ok_or(SSZError::InvalidByte)?;
-            let total = size * N;
-
-            if data.len() != total {
-                return Err(SSZError::InvalidLength {
-                    expected: total,
-                    got: data.len(),
-                });
-            }
+    let expected = match T::fixed_size() {
+        Some(size) if T::is_fixed_size() => size * N,
+        _ => BYTES_PER_LENGTH_OFFSET * N,
+    };
+    if (T::is_fixed_size() && data.len() != expected) || data.len() < expected {
+        return Err(SSZError::InvalidLength {
+            expected,
+            got: data.len(),
+        });
+    }
+    Ok(())
+}
 
-            let mut out_fixed: Vec<T> = Vec::with_capacity(N);
-            for i in 0..N {
-                let start = i * size;
-                let end = start + size;
-                let item = T::deserialize(&data[start..end])?;
-                out_fixed.push(item);
-            }
+/// Returns the encoding of element `index` of a fixed-length sequence of `N` elements.
+///
+/// `data` must have passed [`check_elements_len`].
+fn element_bytes<T, const N: usize>(data: &[u8], index: usize) -> Result<&[u8], SSZError>
+where
+    T: SszTypeInfo,
+{
+    if let Some(size) = T::fixed_size().filter(|_| T::is_fixed_size()) {
+        return Ok(&data[index * size..(index + 1) * size]);
+    }
 
-            out_fixed
-                .clone()
+    let read_offset = |i: usize| {
+        let start = i * BYTES_PER_LENGTH_OFFSET;
+        let offset = u32::from_le_bytes(
+            data[start..start + BYTES_PER_LENGTH_OFFSET]
                 .try_into()
-                .map_err(|_| SSZError::InvalidLength {
-                    expected: N,
-                    got: out_fixed.len(),
-                })
+                .unwrap(),
+        ) as usize;
+        if offset > data.len() {
+            Err(SSZError::OffsetOutOfBounds)
         } else {
-            let offset_bytes_len = BYTES_PER_LENGTH_OFFSET * N;
-            if data.len() < offset_bytes_len {
-                return Err(SSZError::InvalidLength {
-                    expected: offset_bytes_len,
-                    got: data.len(),
-                });
-            }
+            Ok(offset)
+        }
+    };
+    let start = read_offset(index)?;
+    let end = if index + 1 < N {
+        read_offset(index + 1)?
+    } else {
+        data.len()
+    };
+    if start > end {
+        return Err(SSZError::InvalidOffsetRange { start, end });
+    }
+    Ok(&data[start..end])
+}
 
-            let mut offsets = Vec::with_capacity(N);
-            for i in 0..N {
-                let start = i * BYTES_PER_LENGTH_OFFSET;
-                let end = start + BYTES_PER_LENGTH_OFFSET;
-                let offset = u32::from_le_bytes(data[start..end].try_into().unwrap()) as usize;
-                if offset > data.len() {
-                    return Err(SSZError::OffsetOutOfBounds);
-                }
-                offsets.push(offset);
-            }
+/// Builds an array from `f(0)..f(N - 1)`, stopping at the first error.
+///
+/// Elements already built when `f` fails are dropped, not leaked.
+pub(crate) fn try_array_from_fn<T, const N: usize>(
+    mut f: impl FnMut(usize) -> Result<T, SSZError>,
+) -> Result<[T; N], SSZError> {
+    struct Guard<'a, T, const N: usize> {
+        array: &'a mut [MaybeUninit<T>; N],
+        initialized: usize,
+    }
 
-            let mut out_var: Vec<T> = Vec::with_capacity(N);
-            for i in 0..N {
-                let start = offsets[i];
-                let end = if i + 1 < N {
-                    offsets[i + 1]
-                } else {
-                    data.len()
-                };
-                if start > end || end > data.len() {
-                    return Err(SSZError::InvalidOffsetRange { start, end });
-                }
-                let item = T::deserialize(&data[start..end])?;
-                out_var.push(item);
+    impl<T, const N: usize> Drop for Guard<'_, T, N> {
+        fn drop(&mut self) {
+            for slot in &mut self.array[..self.initialized] {
+                // SAFETY: the first `initialized` slots were written by the loop below.
+                unsafe { slot.assume_init_drop() };
             }
-
-            out_var
-                .clone()
-                .try_into()
-                .map_err(|_| SSZError::InvalidLength {
-                    expected: N,
-                    got: out_var.len(),
-                })
         }
     }
+
+    let mut array = [const { MaybeUninit::uninit() }; N];
+    let mut guard = Guard {
+        array: &mut array,
+        initialized: 0,
+    };
+    while guard.initialized < N {
+        let item = f(guard.initialized)?;
+        guard.array[guard.initialized].write(item);
+        guard.initialized += 1;
+    }
+    core::mem::forget(guard);
+
+    // SAFETY: all `N` slots are initialized, and `MaybeUninit<T>` has the layout of `T`.
+    Ok(unsafe { core::mem::transmute_copy::<_, [T; N]>(&array) })
+}
+
+/// Implements deserialization for list.
+///
+/// Elements are decoded straight into the array, with no intermediate heap allocation. Arrays
+/// are returned by value, so for arrays larger than a few hundred KiB prefer `Box<[T; N]>`,
+/// which never places the array on the stack.
+impl<T, const N: usize> SimpleDeserialize for [T; N]
+where
+    T: SimpleDeserialize + SszTypeInfo,
+{
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        check_elements_len::<T, N>(data)?;
+        try_array_from_fn(|i| T::deserialize(element_bytes::<T, N>(data, i)?))
+    }
+}
+
+/// Provides `SszTypeInfo` for heap-allocated arrays.
+impl<T, const N: usize> SszTypeInfo for Box<[T; N]>
+where
+    T: SszTypeInfo,
+{
+    const IS_FIXED: bool = <[T; N]>::IS_FIXED;
+
+    fn fixed_size() -> Option<usize> {
+        <[T; N]>::fixed_size()
+    }
+}
+
+/// Implements serialization for heap-allocated arrays, same as `[T; N]`.
+impl<T, const N: usize> SimpleSerialize for Box<[T; N]>
+where
+    T: SimpleSerialize + Clone + SszTypeInfo,
+{
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        (**self).serialize(buffer)
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        (**self).ssz_bytes_len()
+    }
+}
+
+/// Implements deserialization for heap-allocated arrays without a stack temporary.
+impl<T, const N: usize> SimpleDeserialize for Box<[T; N]>
+where
+    T: SimpleDeserialize + SszTypeInfo,
+{
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        check_elements_len::<T, N>(data)?;
+        let elements = (0..N)
+            .map(|i| T::deserialize(element_bytes::<T, N>(data, i)?))
+            .collect::<Result<Vec<T>, SSZError>>()?;
+        let len = elements.len();
+        elements
+            .into_boxed_slice()
+            .try_into()
+            .map_err(|_| SSZError::InvalidLength {
+                expected: N,
+                got: len,
+            })
+    }
+}
+
+/// Implements `hash_tree_root` for heap-allocated arrays, same as `[T; N]`.
+impl<T, const N: usize> Merkleize for Box<[T; N]>
+where
+    T: SimpleSerialize + SszTypeInfo + Clone + Merkleize,
+{
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        (**self).hash_tree_root()
+    }
+
+    fn chunk_count() -> usize {
+        <[T; N]>::chunk_count()
+    }
 }
 
 /// Implements `hash_tree_root` for List.
@@ -187,6 +285,7 @@ mod tests {
 
     use crate::ssz::SimpleDeserialize;
     use crate::{Merkleize, SimpleSerialize};
+    use alloc::boxed::Box;
     use alloc::vec;
     use alloy_primitives::{
         B256,
@@ -233,6 +332,24 @@ mod tests {
         assert_eq!(a, recovered_a);
     }
 
+    #[test]
+    fn test_large_arrays() {
+        let a = [7u8; 65536];
+        let mut buffer = vec![];
+        a.serialize(&mut buffer).unwrap();
+        let recovered = <[u8; 65536]>::deserialize(&buffer).unwrap();
+        assert_eq!(a, recovered);
+
+        let data = vec![9u8; 1_000_000];
+        let boxed = Box::<[u8; 1_000_000]>::deserialize(&data).unwrap();
+        assert!(boxed.iter().all(|&b| b == 9));
+        let mut buffer = vec![];
+        boxed.serialize(&mut buffer).unwrap();
+        assert_eq!(buffer, data);
+
+        assert!(Box::<[u8; 1_000_000]>::deserialize(&data[1..]).is_err());
+    }
+
     #[test]
     fn test_ssz_merkle() {
         let a: [u16; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
@@ -249,4 +366,41 @@ mod tests {
             hex::encode(root)
         );
     }
+
+    #[test]
+    fn test_deserialize_array_in_place() {
+        let values: [u32; 100] = core::array::from_fn(|i| i as u32 * 3);
+        let mut buffer = vec![];
+        values.serialize(&mut buffer).unwrap();
+        assert_eq!(<[u32; 100]>::deserialize(&buffer), Ok(values));
+
+        let nested = [vec![1u8], vec![], vec![2, 3]];
+        let mut buffer = vec![];
+        nested.serialize(&mut buffer).unwrap();
+        assert_eq!(<[vec::Vec<u8>; 3]>::deserialize(&buffer), Ok(nested));
+    }
+
+    #[test]
+    fn test_deserialize_array_drops_decoded_elements_on_error() {
+        use crate::{SSZError, ssz::Tracked};
+
+        let mut values = [0u32; 100];
+        values[50] = u32::MAX;
+        let mut buffer = vec![];
+        values.serialize(&mut buffer).unwrap();
+
+        assert_eq!(
+            <[Tracked<1>; 100]>::deserialize(&buffer).unwrap_err(),
+            SSZError::InvalidByte
+        );
+        assert_eq!(Tracked::<1>::live(), 0);
+
+        values[50] = 0;
+        let mut buffer = vec![];
+        values.serialize(&mut buffer).unwrap();
+        let decoded = <[Tracked<1>; 100]>::deserialize(&buffer).unwrap();
+        assert_eq!(Tracked::<1>::live(), 100);
+        drop(decoded);
+        assert_eq!(Tracked::<1>::live(), 0);
+    }
 }
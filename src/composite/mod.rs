@@ -5,6 +5,9 @@ extern crate alloc;
 mod bitvector;
 pub use bitvector::*;
 
+mod array_bitvector;
+pub use array_bitvector::*;
+
 pub mod vectors;
 
 pub mod bitlist;
@@ -18,7 +21,21 @@ pub mod union;
 
 pub mod fixed_byte;
 
+pub mod bytes;
+
+pub mod cached;
+pub use cached::*;
+
+pub mod byte_list;
+pub use byte_list::*;
+
+pub mod cow;
+
 pub mod container;
 
 pub mod fixed_vectors;
 pub mod ssz_list;
+
+pub mod raw_list;
+
+pub mod tuple;
@@ -1,7 +1,9 @@
 //! Serializes,deserializes and merkleization of options.
 
 use crate::SimpleDeserialize;
-use crate::{Merkleize, SSZError, SimpleSerialize, SszTypeInfo, merkleization::mix_in_selector};
+use crate::{
+    Merkleize, MixInKind, SSZError, SimpleSerialize, SszTypeInfo, merkleization::mix_in_selector,
+};
 use alloc::vec::Vec;
 use alloy_primitives::B256;
 
@@ -10,18 +12,12 @@ where
     T: SszTypeInfo,
 {
     /// Indicates that the option is variable-size.
-    fn is_fixed_size() -> bool {
-        false
-    }
+    const IS_FIXED: bool = false;
 
     /// Returns None since the size of an option is not fixed.
     fn fixed_size() -> Option<usize> {
         None
     }
-
-    fn is_basic_type() -> bool {
-        T::is_basic_type()
-    }
 }
 
 impl<T> SimpleSerialize for Option<T>
@@ -42,6 +38,10 @@ where
         }
         Ok(buffer.len() - start_len)
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(1 + self.as_ref().map_or(Ok(0), T::ssz_bytes_len)?)
+    }
 }
 
 impl<T> SimpleDeserialize for Option<T>
@@ -82,6 +82,10 @@ where
             None => Ok(mix_in_selector(B256::ZERO, 0)),
         }
     }
+
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Selector
+    }
 }
 
 #[cfg(test)]
@@ -170,4 +174,27 @@ mod tests {
         ));
         assert_eq!(hashed_tree_root.unwrap(), recovered_tree);
     }
+
+    #[test]
+    fn test_option_inside_vec() {
+        use crate::SszTypeInfo;
+        use alloc::vec::Vec;
+
+        let values: Vec<Option<u64>> = vec![Some(7), None, Some(9)];
+        let mut buffer = vec![];
+        values.serialize(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 3 * 4 + 9 + 1 + 9);
+        assert_eq!(Vec::<Option<u64>>::deserialize(&buffer).unwrap(), values);
+
+        // An option is a union, never a basic type, so the vector merkleizes element roots.
+        assert!(!Option::<u64>::is_basic_type());
+        let roots: Vec<[u8; 32]> = values
+            .iter()
+            .map(|value| value.hash_tree_root().unwrap().0)
+            .collect();
+        assert_eq!(
+            values.hash_tree_root().unwrap(),
+            crate::merkleization::merkleize(&roots, None).unwrap()
+        );
+    }
 }
@@ -0,0 +1,138 @@
+//! Lazily decoded list of variable-size elements that re-serializes byte-for-byte.
+
+use crate::{SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+/// SSZ list of variable-size elements kept in encoded form.
+///
+/// Deserializing only validates the offset table; elements are decoded on demand with
+/// [`RawList::get`], and serializing writes back the original bytes unchanged.
+///
+/// Only variable-size element types are supported; `RawList` of a fixed-size type fails to
+/// compile once it is deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawList<T> {
+    data: Vec<u8>,
+    ranges: Vec<Range<usize>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> RawList<T> {
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the encoded bytes of the element at `index`.
+    pub fn get_raw(&self, index: usize) -> Option<&[u8]> {
+        self.ranges
+            .get(index)
+            .map(|range| &self.data[range.clone()])
+    }
+
+    /// Returns the encoded list.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<T> RawList<T>
+where
+    T: SimpleDeserialize,
+{
+    /// Decodes the element at `index`.
+    pub fn get(&self, index: usize) -> Result<T, SSZError> {
+        let raw = self.get_raw(index).ok_or(SSZError::InvalidLength {
+            expected: self.len(),
+            got: index,
+        })?;
+        T::deserialize(raw)
+    }
+}
+
+impl<T> SszTypeInfo for RawList<T> {
+    const IS_FIXED: bool = false;
+
+    fn fixed_size() -> Option<usize> {
+        None
+    }
+}
+
+/// Writes back the bytes the list was decoded from.
+impl<T> SimpleSerialize for RawList<T> {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        buffer.extend_from_slice(&self.data);
+        Ok(self.data.len())
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        Ok(self.data.len())
+    }
+}
+
+/// Validates the offset table without decoding any element.
+impl<T> SimpleDeserialize for RawList<T>
+where
+    T: SszTypeInfo,
+{
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        const { assert!(!T::IS_FIXED, "RawList elements must be variable-size") };
+
+        let ranges = crate::vectors::variable_element_ranges(data)?;
+        Ok(Self {
+            data: data.to_vec(),
+            ranges,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_raw_list_roundtrip_is_byte_identical() {
+        let original: Vec<Vec<u8>> = vec![vec![1, 2], vec![], vec![3, 4, 5]];
+        let mut buffer = vec![];
+        original.serialize(&mut buffer).expect("can serialize");
+
+        let raw = RawList::<Vec<u8>>::deserialize(&buffer).expect("can deserialize");
+        assert_eq!(raw.len(), 3);
+        assert_eq!(raw.get(0), Ok(vec![1, 2]));
+        assert_eq!(raw.get(1), Ok(vec![]));
+        assert_eq!(raw.get(2), Ok(vec![3, 4, 5]));
+        assert_eq!(raw.get_raw(2), Some([3u8, 4, 5].as_ref()));
+        assert!(raw.get(3).is_err());
+
+        let mut reencoded = vec![];
+        raw.serialize(&mut reencoded).expect("can serialize");
+        assert_eq!(reencoded, buffer);
+        assert_eq!(raw.ssz_bytes_len(), Ok(buffer.len()));
+
+        let empty = RawList::<Vec<u8>>::deserialize(&[]).expect("can deserialize");
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_raw_list_rejects_bad_offsets() {
+        let mut data = 8u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&6u32.to_le_bytes());
+        assert_eq!(
+            RawList::<Vec<u8>>::deserialize(&data),
+            Err(SSZError::InvalidOffsetRange { start: 8, end: 6 })
+        );
+
+        let data = 6u32.to_le_bytes();
+        assert_eq!(
+            RawList::<Vec<u8>>::deserialize(&data),
+            Err(SSZError::OffsetOutOfBounds)
+        );
+    }
+}
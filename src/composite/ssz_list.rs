@@ -1,21 +1,37 @@
 //! Contains serialization,deserialization amd merkleization for ssz compatible list types.
 
 use crate::{
-    Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
-    merkleization::{merkleize, mix_in_length, pack},
+    Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
+    merkleization::{
+        BYTES_PER_CHUNK, CachedTree, MerkleProof, get_depth, merkleize, mix_in_length, pack,
+    },
 };
 use alloc::vec::Vec;
 use alloy_primitives::B256;
 use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// SSZ `List[T, N]` holding at most `N` elements.
+///
+/// `List<T, 0>` is allowed and can only ever be empty; its root is
+/// `mix_in_length(B256::ZERO, 0)`.
+#[derive(Debug, Clone)]
 pub struct List<T, const N: usize> {
     elements: Vec<T>,
+    // chunk tree kept up to date by `push_and_root`, dropped on any other mutation
+    cache: Option<CachedTree>,
     // enforce max length at compile time
     _phantom: PhantomData<[T; N]>,
 }
 
+impl<T: PartialEq, const N: usize> PartialEq for List<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for List<T, N> {}
+
 impl<T, const N: usize> List<T, N> {
     pub fn new(elements: Vec<T>) -> Result<Self, SSZError> {
         if elements.len() > N {
@@ -26,11 +42,19 @@ impl<T, const N: usize> List<T, N> {
         } else {
             Ok(Self {
                 elements,
+                cache: None,
                 _phantom: PhantomData,
             })
         }
     }
 
+    /// Collects the items of `iter`, failing as soon as it yields more than `N`.
+    ///
+    /// An overlong iterator is not drained, so the error reports `N + 1` elements.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, SSZError> {
+        Self::new(iter.into_iter().take(N.saturating_add(1)).collect())
+    }
+
     pub fn into_inner(self) -> Vec<T> {
         self.elements
     }
@@ -42,6 +66,156 @@ impl<T, const N: usize> List<T, N> {
     pub fn is_empty(&self) -> bool {
         self.elements.is_empty()
     }
+
+    /// Appends `value`, failing if the list already holds `N` elements.
+    pub fn push(&mut self, value: T) -> Result<(), SSZError> {
+        self.check_not_full()?;
+        self.elements.push(value);
+        self.cache = None;
+        Ok(())
+    }
+
+    /// Removes and returns the last element, or `None` if the list is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.elements.pop()?;
+        self.cache = None;
+        Some(value)
+    }
+
+    /// Inserts `value` at `index`, shifting later elements back.
+    ///
+    /// Fails with `InvalidLength` if the list already holds `N` elements, or with
+    /// `IndexOutOfBounds` if `index` is past the end.
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), SSZError> {
+        self.check_not_full()?;
+        if index > self.len() {
+            return Err(SSZError::IndexOutOfBounds {
+                index,
+                len: self.len(),
+            });
+        }
+        self.elements.insert(index, value);
+        self.cache = None;
+        Ok(())
+    }
+
+    fn check_not_full(&self) -> Result<(), SSZError> {
+        if self.len() >= N {
+            return Err(SSZError::InvalidLength {
+                expected: N,
+                got: self.len() + 1,
+            });
+        }
+        Ok(())
+    }
+
+    /// Drops the elements beyond `len`; does nothing if the list is not longer than `len`.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.elements.len() {
+            self.elements.truncate(len);
+            self.cache = None;
+        }
+    }
+}
+
+impl<T, const N: usize> List<T, N>
+where
+    T: Merkleize + SimpleSerialize + SszTypeInfo,
+{
+    /// Returns the chunk limit the list's Merkle tree is padded to, as in the spec.
+    fn chunk_limit() -> Option<usize> {
+        Some(<Self as Merkleize>::chunk_count())
+    }
+
+    /// Returns the leaf chunks of the list's Merkle tree.
+    fn chunks(&self) -> Result<Vec<[u8; BYTES_PER_CHUNK]>, SSZError> {
+        if T::is_basic_type() {
+            let mut serialized = Vec::new();
+            self.elements.serialize(&mut serialized)?;
+            Ok(pack(&serialized))
+        } else {
+            self.elements
+                .iter()
+                .map(|e| e.hash_tree_root().map(|h| h.0))
+                .collect()
+        }
+    }
+
+    /// Returns the root of the list's data tree, before the length is mixed in.
+    ///
+    /// `hash_tree_root` is `mix_in_length(self.data_tree_root()?, self.len())`.
+    pub fn data_tree_root(&self) -> Result<B256, SSZError> {
+        merkleize(&self.chunks()?, Self::chunk_limit())
+    }
+
+    /// Proves the element at `index` against `hash_tree_root`.
+    ///
+    /// The leaf is the element's root, or for basic element types the packed chunk holding the
+    /// element. The branch ends with the length chunk, so the generalized index counts the
+    /// length mix-in as one level.
+    pub fn prove(&self, index: usize) -> Result<MerkleProof, SSZError> {
+        if index >= self.len() {
+            return Err(SSZError::IndexOutOfBounds {
+                index,
+                len: self.len(),
+            });
+        }
+        let chunk_index = match T::is_basic_type() {
+            true => {
+                index * T::fixed_size().expect("Basic types should have fixed size")
+                    / BYTES_PER_CHUNK
+            }
+            false => index,
+        };
+
+        let mut proof = CachedTree::new(&self.chunks()?, Self::chunk_limit()).prove(chunk_index);
+        // The data tree is the left child of the length mix-in.
+        proof.index += 1 << proof.branch.len();
+        let mut length = [0u8; BYTES_PER_CHUNK];
+        length[..8].copy_from_slice(&(self.len() as u64).to_le_bytes());
+        proof.branch.push(B256::from(length));
+        Ok(proof)
+    }
+
+    /// Appends `value` and returns the new `hash_tree_root`.
+    ///
+    /// The chunk tree is cached between calls, so each push only rehashes the path from the
+    /// changed chunk to the root plus the length mix-in.
+    pub fn push_and_root(&mut self, value: T) -> Result<B256, SSZError> {
+        self.check_not_full()?;
+
+        let mut cache = match self.cache.take() {
+            Some(cache) => cache,
+            None => CachedTree::new(&self.chunks()?, Self::chunk_limit()),
+        };
+
+        if T::is_basic_type() {
+            let elem_size = T::fixed_size().expect("Basic types should have fixed size");
+            let offset = self.len() * elem_size;
+            let index = offset / BYTES_PER_CHUNK;
+            let mut chunk = cache.chunk(index);
+            let mut serialized = Vec::with_capacity(elem_size);
+            value.serialize(&mut serialized)?;
+            let start = offset % BYTES_PER_CHUNK;
+            chunk[start..start + elem_size].copy_from_slice(&serialized);
+            cache.set_chunk(index, chunk);
+        } else {
+            cache.set_chunk(self.len(), value.hash_tree_root()?.0);
+        }
+
+        self.elements.push(value);
+        let root = mix_in_length(cache.root(), self.len());
+        self.cache = Some(cache);
+        Ok(root)
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for List<T, N> {
+    type Error = SSZError;
+
+    fn try_from(elements: Vec<T>) -> Result<Self, Self::Error> {
+        Self::new(elements)
+    }
 }
 
 // Optional: allow treating List<T, N> like a Vec<T>
@@ -53,6 +227,7 @@ impl<T, const N: usize> Deref for List<T, N> {
 }
 impl<T, const N: usize> DerefMut for List<T, N> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.cache = None;
         &mut self.elements
     }
 }
@@ -61,9 +236,7 @@ impl<T, const N: usize> SszTypeInfo for List<T, N>
 where
     T: SszTypeInfo,
 {
-    fn is_fixed_size() -> bool {
-        false
-    }
+    const IS_FIXED: bool = false;
 
     fn fixed_size() -> Option<usize> {
         None
@@ -84,6 +257,21 @@ where
         }
         self.elements.serialize(buffer)
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        self.elements.ssz_bytes_len()
+    }
+
+    #[cfg(feature = "std")]
+    fn serialize_to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<usize> {
+        if self.len() > N {
+            return Err(std::io::Error::other(SSZError::InvalidLength {
+                expected: N,
+                got: self.len(),
+            }));
+        }
+        self.elements.serialize_to_writer(writer)
+    }
 }
 
 /// Deserializes a ssz compatible list
@@ -101,6 +289,7 @@ where
         }
         Ok(List {
             elements: vec,
+            cache: None,
             _phantom: PhantomData,
         })
     }
@@ -112,29 +301,36 @@ where
     T: Merkleize + SimpleSerialize + SszTypeInfo,
 {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        let chunks = if T::is_basic_type() {
-            let mut serialized = Vec::new();
-            self.serialize(&mut serialized)?;
-            pack(&serialized)
+        Ok(mix_in_length(self.data_tree_root()?, self.len()))
+    }
+
+    fn chunk_count() -> usize {
+        if T::is_basic_type() {
+            let elem_size = T::fixed_size().expect("Basic types should have fixed size");
+            (N * elem_size).div_ceil(32)
         } else {
-            self.elements
-                .iter()
-                .map(|e| e.hash_tree_root().map(|h| h.0))
-                .collect::<Result<Vec<_>, _>>()?
-        };
+            N
+        }
+    }
+
+    /// Adds the length mix-in layer.
+    fn merkle_depth() -> u32 {
+        get_depth(Self::chunk_count()) + 1
+    }
 
-        let root = merkleize(&chunks, None)?; // list: no forced chunk count
-        Ok(mix_in_length(root, self.len()))
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Length
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::List;
-    use crate::{Merkleize, SimpleDeserialize, SimpleSerialize};
+    use crate::{Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize};
     use alloc::vec;
+    use alloc::vec::Vec;
     use alloy_primitives::{
-        B256,
+        B256, U256,
         hex::{self, FromHex},
     };
 
@@ -179,6 +375,136 @@ mod tests {
         assert_eq!(result.unwrap().len(), 4);
     }
 
+    #[test]
+    fn test_list_truncate() {
+        let mut list = List::<u8, 8>::new(vec![1, 2, 3, 4, 5]).unwrap();
+        list.truncate(2);
+        assert_eq!(list.len(), 2);
+        assert_eq!(&*list, &[1, 2]);
+
+        list.truncate(6);
+        assert_eq!(list.len(), 2);
+
+        let root = list.push_and_root(9).unwrap();
+        assert_eq!(
+            root,
+            List::<u8, 8>::new(vec![1, 2, 9])
+                .unwrap()
+                .hash_tree_root()
+                .unwrap()
+        );
+        list.truncate(1);
+        let root = list.push_and_root(7).unwrap();
+        assert_eq!(
+            root,
+            List::<u8, 8>::new(vec![1, 7])
+                .unwrap()
+                .hash_tree_root()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_push_pop_insert() {
+        let mut list = List::<u8, 4>::new(vec![1, 4]).unwrap();
+        list.insert(1, 2).unwrap();
+        list.insert(2, 3).unwrap();
+        assert_eq!(&*list, &[1, 2, 3, 4]);
+
+        assert_eq!(
+            list.push(5),
+            Err(SSZError::InvalidLength {
+                expected: 4,
+                got: 5
+            })
+        );
+        assert_eq!(
+            list.insert(0, 0),
+            Err(SSZError::InvalidLength {
+                expected: 4,
+                got: 5
+            })
+        );
+        assert_eq!(list.len(), 4);
+
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(
+            list.insert(4, 9),
+            Err(SSZError::IndexOutOfBounds { index: 4, len: 3 })
+        );
+        list.push(9).unwrap();
+        assert_eq!(&*list, &[1, 2, 3, 9]);
+
+        // Mutations drop the chunk tree cached by `push_and_root`.
+        let mut list = List::<u8, 4>::new(vec![]).unwrap();
+        list.push_and_root(1).unwrap();
+        list.insert(0, 7).unwrap();
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(
+            list.push_and_root(8).unwrap(),
+            List::<u8, 4>::new(vec![7, 8])
+                .unwrap()
+                .hash_tree_root()
+                .unwrap()
+        );
+        assert_eq!(List::<u8, 4>::new(vec![]).unwrap().pop(), None);
+    }
+
+    #[test]
+    fn test_list_try_from() {
+        let list = List::<u16, 4>::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(&*list, &[1, 2, 3]);
+        assert_eq!(
+            List::<u16, 2>::try_from(vec![1, 2, 3]),
+            Err(SSZError::InvalidLength {
+                expected: 2,
+                got: 3
+            })
+        );
+
+        let list = List::<u16, 4>::try_from_iter((1..=4).map(|i| i * 10)).unwrap();
+        assert_eq!(&*list, &[10, 20, 30, 40]);
+        assert!(
+            List::<u16, 4>::try_from_iter(core::iter::empty())
+                .unwrap()
+                .is_empty()
+        );
+        // An unbounded iterator stops being consumed once it overflows.
+        assert_eq!(
+            List::<u16, 4>::try_from_iter(0..),
+            Err(SSZError::InvalidLength {
+                expected: 4,
+                got: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_zero_capacity_list() {
+        let empty = List::<u8, 0>::new(vec![]).expect("empty list fits capacity 0");
+        assert!(empty.is_empty());
+
+        let result = List::<u8, 0>::new(vec![1]);
+        assert_eq!(
+            result,
+            Err(crate::SSZError::InvalidLength {
+                expected: 0,
+                got: 1
+            })
+        );
+
+        let mut buffer = vec![];
+        empty.serialize(&mut buffer).unwrap();
+        assert!(buffer.is_empty());
+        assert_eq!(List::<u8, 0>::deserialize(&buffer).unwrap(), empty);
+        assert!(List::<u8, 0>::deserialize(&[1]).is_err());
+
+        assert_eq!(
+            empty.hash_tree_root().unwrap(),
+            crate::merkleization::mix_in_length(B256::ZERO, 0)
+        );
+    }
+
     #[test]
     fn test_ssz_merkle_list_root() {
         let list = List::<u16, 10>::new(vec![1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
@@ -197,10 +523,251 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_u256_list_merkle_root() {
+        // Each U256 fills a whole chunk; the limit of 4 pads two values to a depth-2 tree.
+        let list = List::<U256, 4>::new(vec![
+            U256::from(1),
+            U256::from_str_radix(
+                "8000000000000000000000000000000000000000000000000000000000001234",
+                16,
+            )
+            .unwrap(),
+        ])
+        .unwrap();
+        let expected =
+            B256::from_hex("0x514a67bc94a300bffa71323a561b6efea0cf5ade02b3011cbc99ecb15530b3c5")
+                .expect("valid hex");
+        assert_eq!(list.hash_tree_root().unwrap(), expected);
+        assert_eq!(List::<U256, 4>::chunk_count(), 4);
+    }
+
+    #[test]
+    fn test_list_of_byte_lists_merkle_root() {
+        // Transaction-list shape: each inner byte list mixes in its own length, then the outer
+        // list pads three roots to its limit of 4 and mixes in 3.
+        let txs: vec::Vec<vec::Vec<u8>> = vec![vec![1], vec![2, 3], vec![4, 5, 6]];
+        let list = List::<List<u8, 32>, 4>::new(
+            txs.iter()
+                .map(|tx| List::new(tx.clone()).unwrap())
+                .collect(),
+        )
+        .unwrap();
+        let expected =
+            B256::from_hex("0xca35dfd57cc706cc6b36edca7229e750a09fa5d0fd2f68c245cb4d3ccf5a8408")
+                .expect("valid hex");
+        assert_eq!(list.hash_tree_root().unwrap(), expected);
+
+        // A bare `Vec<Vec<u8>>` merkleizes with vector semantics, so it has no length mix-ins.
+        assert_ne!(txs.hash_tree_root().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_list_data_tree_root() {
+        use crate::merkleization::mix_in_length;
+
+        let list = List::<u16, 40>::new(vec![1, 2, 3]).unwrap();
+        let data_root = list.data_tree_root().unwrap();
+        assert_eq!(
+            mix_in_length(data_root, list.len()),
+            list.hash_tree_root().unwrap()
+        );
+
+        let nested = List::<List<u8, 4>, 3>::new(vec![List::new(vec![9]).unwrap()]).unwrap();
+        assert_eq!(
+            mix_in_length(nested.data_tree_root().unwrap(), nested.len()),
+            nested.hash_tree_root().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_prove() {
+        // Limit 16 u64s packs into 4 chunks; element 5 sits in chunk 1.
+        let list = List::<u64, 16>::new((0..7).collect()).unwrap();
+        let proof = list.prove(5).unwrap();
+        assert_eq!(proof.index, 9);
+        assert_eq!(proof.branch.len(), 3);
+        assert_eq!(proof.root(), Ok(list.hash_tree_root().unwrap()));
+
+        let nested = List::<List<u8, 4>, 3>::new(vec![
+            List::new(vec![1]).unwrap(),
+            List::new(vec![2, 3]).unwrap(),
+        ])
+        .unwrap();
+        let proof = nested.prove(1).unwrap();
+        assert_eq!(proof.index, 9);
+        assert_eq!(proof.leaf, nested[1].hash_tree_root().unwrap());
+        assert_eq!(proof.root(), Ok(nested.hash_tree_root().unwrap()));
+
+        // Indices past the length are rejected even when they fit under the limit.
+        assert_eq!(
+            nested.prove(2).unwrap_err(),
+            SSZError::IndexOutOfBounds { index: 2, len: 2 }
+        );
+
+        // A proof against a 2^40 limit is 40 zero-padded levels plus the length.
+        let wide = List::<u64, { 1 << 42 }>::new((0..7).collect()).unwrap();
+        let proof = wide.prove(5).unwrap();
+        assert_eq!(proof.branch.len(), 41);
+        assert_eq!(proof.index, (1 << 41) + 1);
+        assert_eq!(proof.root(), Ok(wide.hash_tree_root().unwrap()));
+    }
+
+    #[test]
+    fn test_list_merkle_depth() {
+        assert_eq!(List::<u64, 4>::chunk_count(), 1);
+        assert_eq!(List::<u64, 4>::merkle_depth(), 1);
+        assert_eq!(List::<u64, 16>::chunk_count(), 4);
+        assert_eq!(List::<u64, 16>::merkle_depth(), 3);
+        assert_eq!(List::<List<u8, 4>, 5>::chunk_count(), 5);
+        assert_eq!(List::<List<u8, 4>, 5>::merkle_depth(), 4);
+    }
+
+    #[test]
+    fn test_list_mix_in_kind() {
+        assert_eq!(List::<u64, 4>::mix_in_kind(), MixInKind::Length);
+        assert_eq!(vec::Vec::<u64>::mix_in_kind(), MixInKind::None);
+    }
+
+    #[test]
+    fn test_list_push_and_root() {
+        let mut list = List::<u16, 40>::new(vec![]).unwrap();
+        for i in 0..35u16 {
+            let root = list.push_and_root(i * 3).expect("within capacity");
+            assert_eq!(root, list.hash_tree_root().unwrap());
+            let fresh = List::<u16, 40>::new(list.to_vec()).unwrap();
+            assert_eq!(root, fresh.hash_tree_root().unwrap());
+        }
+
+        list[0] = 0xffff;
+        let root = list.push_and_root(7).expect("within capacity");
+        assert_eq!(root, list.hash_tree_root().unwrap());
+
+        let mut nested = List::<List<u8, 4>, 3>::new(vec![]).unwrap();
+        for i in 0..3u8 {
+            let root = nested
+                .push_and_root(List::new(vec![i; i as usize]).unwrap())
+                .expect("within capacity");
+            assert_eq!(root, nested.hash_tree_root().unwrap());
+        }
+        assert!(nested.push_and_root(List::new(vec![]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_nested_byte_list_merkle_root() {
+        // Each inner byte list mixes in its own length, then the outer list mixes in its count.
+        let list = List::<List<u8, 32>, 4>::new(vec![
+            List::new(vec![1, 2]).unwrap(),
+            List::new(vec![]).unwrap(),
+            List::new(vec![3, 4, 5]).unwrap(),
+        ])
+        .unwrap();
+        let expected =
+            B256::from_hex("0x6d17b8a2bf56cbe68e00c0b3e2f4073d33c2d6daa3e5d3600b86c3e0e398e4bd")
+                .expect("valid hex");
+        assert_eq!(list.hash_tree_root().unwrap(), expected);
+
+        // A bare `Vec<u8>` has vector semantics, so only the outer length is mixed in.
+        let list = List::<vec::Vec<u8>, 4>::new(vec![vec![1, 2], vec![], vec![3, 4, 5]]).unwrap();
+        let expected =
+            B256::from_hex("0x1a3405311f9c6b4eb9c7f0cf69c370b5ac5394176b02e5a3c91248443f5a91cd")
+                .expect("valid hex");
+        assert_eq!(list.hash_tree_root().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_composite_list_pads_to_limit() {
+        use crate::container::Foo;
+
+        let list = List::<Foo, 8>::new(vec![
+            Foo { a: 1, b: 2 },
+            Foo { a: 3, b: 4 },
+            Foo { a: 5, b: 6 },
+        ])
+        .unwrap();
+        // Three element roots padded to a depth-3 tree of eight leaves, then the length.
+        let expected =
+            B256::from_hex("0x248661eea4208ca0698716367ed57ff481e6f134c91a7780b7be02f4db094cda")
+                .expect("valid hex");
+        assert_eq!(list.hash_tree_root().unwrap(), expected);
+        assert_eq!(List::<Foo, 8>::merkle_depth(), 4);
+    }
+
+    #[test]
+    fn test_basic_list_pads_to_limit() {
+        use crate::merkleization::{hash_tree_root_of_serialized, merkleize, mix_in_length, pack};
+
+        let values: Vec<u64> = (0..10).collect();
+        let list = List::<u64, 2048>::new(values.clone()).unwrap();
+
+        // Ten u64s fill three chunks of a tree padded to 2048 * 8 / 32 = 512 chunks.
+        let mut serialized = vec![];
+        values.serialize(&mut serialized).unwrap();
+        let expected = mix_in_length(merkleize(&pack(&serialized), Some(512)).unwrap(), 10);
+        assert_eq!(list.hash_tree_root().unwrap(), expected);
+
+        let blobs: Vec<_> = values.iter().map(|v| v.to_le_bytes().to_vec()).collect();
+        assert_eq!(
+            hash_tree_root_of_serialized(&blobs, true, Some(8), 2048).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_composite_list_large_limit() {
+        use crate::container::Foo;
+        use crate::merkleization::{merkleize, mix_in_aux, mix_in_length};
+
+        let foos = vec![Foo { a: 1, b: 2 }, Foo { a: 3, b: 4 }, Foo { a: 5, b: 6 }];
+        let roots: Vec<_> = foos.iter().map(|f| f.hash_tree_root().unwrap().0).collect();
+        let mut root = merkleize(&roots, Some(4)).unwrap();
+        let mut zero = merkleize(&[], Some(4)).unwrap();
+        for _ in 2..40 {
+            root = mix_in_aux(root, zero);
+            zero = mix_in_aux(zero, zero);
+        }
+        let expected = mix_in_length(root, 3);
+
+        // A 2^40 limit must not allocate the padding leaves.
+        let list = List::<Foo, { 1 << 40 }>::new(foos.clone()).unwrap();
+        assert_eq!(list.hash_tree_root().unwrap(), expected);
+
+        let mut list = List::<Foo, { 1 << 40 }>::new(vec![]).unwrap();
+        let mut root = B256::ZERO;
+        for foo in foos {
+            root = list.push_and_root(foo).expect("within capacity");
+        }
+        assert_eq!(root, expected);
+    }
+
     #[test]
     fn test_list_merkle_root_differs_by_length() {
         let l1 = List::<u8, 10>::new(vec![1, 2, 3]).unwrap();
         let l2 = List::<u8, 10>::new(vec![1, 2, 3, 4]).unwrap();
         assert_ne!(l1.hash_tree_root().unwrap(), l2.hash_tree_root().unwrap());
     }
+
+    #[test]
+    fn test_list_merkle_root_differs_by_content() {
+        let a = List::<u64, 4>::new(vec![1, 2]).unwrap();
+        let b = List::<u64, 4>::new(vec![2, 1]).unwrap();
+        let c = List::<u64, 4>::new(vec![1, 3]).unwrap();
+        assert_ne!(a.hash_tree_root().unwrap(), b.hash_tree_root().unwrap());
+        assert_ne!(a.hash_tree_root().unwrap(), c.hash_tree_root().unwrap());
+
+        let same = List::<u64, 4>::new(vec![1, 2]).unwrap();
+        assert_eq!(a.hash_tree_root().unwrap(), same.hash_tree_root().unwrap());
+    }
+
+    #[test]
+    fn test_list_merkle_root_differs_by_order() {
+        // Elements spanning several chunks must not be merkleized as a multiset.
+        let a = List::<u64, 16>::new(vec![1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        let b = List::<u64, 16>::new(vec![5, 6, 7, 8, 1, 2, 3, 4]).unwrap();
+        assert_ne!(a.hash_tree_root().unwrap(), b.hash_tree_root().unwrap());
+
+        let a = List::<Vec<u8>, 4>::new(vec![vec![1], vec![2]]).unwrap();
+        let b = List::<Vec<u8>, 4>::new(vec![vec![2], vec![1]]).unwrap();
+        assert_ne!(a.hash_tree_root().unwrap(), b.hash_tree_root().unwrap());
+    }
 }
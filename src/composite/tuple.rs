@@ -0,0 +1,133 @@
+//! Serialization, deserialization and merkleization of tuples as containers.
+
+use crate::container::{serialize_fields, split_fields};
+use crate::merkleization::merkleize_fields;
+use crate::{
+    BYTES_PER_LENGTH_OFFSET, Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
+};
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+
+/// Implements the SSZ traits for a tuple, encoding and merkleizing it like a container whose
+/// fields are the tuple elements in order.
+macro_rules! impl_tuple {
+    ($($ty:ident . $idx:tt),+) => {
+        impl<$($ty: SszTypeInfo),+> SszTypeInfo for ($($ty,)+) {
+            const IS_FIXED: bool = true $(&& $ty::IS_FIXED)+;
+
+            fn fixed_size() -> Option<usize> {
+                let mut size = 0;
+                $(size += $ty::fixed_size()?;)+
+                Some(size)
+            }
+        }
+
+        impl<$($ty: SimpleSerialize + SszTypeInfo),+> SimpleSerialize for ($($ty,)+) {
+            fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+                serialize_fields(
+                    [$((&self.$idx as &dyn SimpleSerialize, $ty::IS_FIXED)),+],
+                    buffer,
+                )
+            }
+
+            fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+                let mut len = 0;
+                $(
+                    if !$ty::IS_FIXED {
+                        len += BYTES_PER_LENGTH_OFFSET;
+                    }
+                    len += self.$idx.ssz_bytes_len()?;
+                )+
+                Ok(len)
+            }
+
+            #[cfg(feature = "std")]
+            fn serialize_to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<usize> {
+                crate::container::write_fields(
+                    [$((&self.$idx as &dyn SimpleSerialize, $ty::IS_FIXED)),+],
+                    writer,
+                )
+            }
+        }
+
+        impl<$($ty: SimpleDeserialize + SszTypeInfo),+> SimpleDeserialize for ($($ty,)+) {
+            fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+                let fields = split_fields(data, &[$($ty::fixed_size()),+])?;
+                Ok(($($ty::deserialize(fields[$idx])?,)+))
+            }
+        }
+
+        impl<$($ty: Merkleize),+> Merkleize for ($($ty,)+) {
+            fn hash_tree_root(&self) -> Result<B256, SSZError> {
+                merkleize_fields(alloc::vec![$(self.$idx.hash_tree_root()?),+])
+            }
+
+            fn chunk_count() -> usize {
+                [$($idx),+].len()
+            }
+        }
+    };
+}
+
+impl_tuple!(A.0);
+impl_tuple!(A.0, B.1);
+impl_tuple!(A.0, B.1, C.2);
+impl_tuple!(A.0, B.1, C.2, D.3);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[derive(crate::SimpleSerialize, crate::SszTypeInfo, crate::Merkleize)]
+    struct Equivalent {
+        a: u32,
+        b: bool,
+        c: Vec<u8>,
+    }
+
+    #[test]
+    fn test_tuple_roundtrip() {
+        let tuple = (7u32, true, vec![1u8, 2, 3]);
+        let mut buffer = vec![];
+        let written = tuple.serialize(&mut buffer).expect("can serialize");
+        assert_eq!(buffer, vec![7, 0, 0, 0, 1, 9, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(written, buffer.len());
+        assert_eq!(tuple.ssz_bytes_len(), Ok(buffer.len()));
+        assert_eq!(<(u32, bool, Vec<u8>)>::deserialize(&buffer), Ok(tuple));
+
+        const { assert!(!<(u32, bool, Vec<u8>)>::IS_FIXED) };
+        assert_eq!(<(u32, bool, Vec<u8>)>::fixed_size(), None);
+        const { assert!(<(u32, bool, u16)>::IS_FIXED) };
+        assert_eq!(<(u32, bool, u16)>::fixed_size(), Some(7));
+        crate::ssz::assert_fixed_size_correct::<(u32, bool, u16)>();
+    }
+
+    #[test]
+    fn test_tuple_matches_container() {
+        let tuple = (7u32, true, vec![1u8, 2, 3]);
+        let container = Equivalent {
+            a: 7,
+            b: true,
+            c: vec![1, 2, 3],
+        };
+
+        let mut tuple_bytes = vec![];
+        tuple.serialize(&mut tuple_bytes).expect("can serialize");
+        let mut container_bytes = vec![];
+        container
+            .serialize(&mut container_bytes)
+            .expect("can serialize");
+        assert_eq!(tuple_bytes, container_bytes);
+
+        assert_eq!(
+            tuple.hash_tree_root().unwrap(),
+            container.hash_tree_root().unwrap()
+        );
+        assert_eq!(<(u32, bool, Vec<u8>)>::chunk_count(), 3);
+    }
+}
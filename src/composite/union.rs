@@ -1,113 +1,225 @@
 // ! Serializes,deserializes and merkleization of union
 
-use crate::SimpleDeserialize;
-use crate::{Merkleize, SSZError, SimpleSerialize, SszTypeInfo, merkleization::mix_in_selector};
+use crate::{MAX_UNION_SELECTOR, SimpleDeserialize};
+use crate::{SSZError, SimpleSerialize, SszTypeInfo};
 use alloc::vec::Vec;
-use alloy_primitives::B256;
 
-/// Basic container for serialization,deserialization and merkleization.
-#[derive(Debug, PartialEq)]
-pub enum MyUnion {
-    None,
-    U32(u32),
-    ByteList(Vec<u8>),
-}
-
-impl SszTypeInfo for MyUnion {
-    /// Returns false since `MyUnion` is not fixed size.
-    fn is_fixed_size() -> bool {
-        false
+/// Splits a serialized union into its selector and payload.
+///
+/// Selectors above 127 are reserved, and selectors in `[variant_count, 128)` do not map to
+/// any variant, so both are rejected as `InvalidSelector`.
+pub fn deserialize_union(data: &[u8], variant_count: u8) -> Result<(u8, &[u8]), SSZError> {
+    if data.is_empty() {
+        return Err(SSZError::ExpectedFurtherInput);
     }
 
-    /// Returns `None` since `MyUnion` is not fixed size.
-    fn fixed_size() -> Option<usize> {
-        None
+    let selector = data[0];
+    if selector > MAX_UNION_SELECTOR {
+        return Err(SSZError::InvalidSelector {
+            selector: selector.into(),
+            reason: "Selector value above 127 is reserved for forward compatibility".into(),
+        });
+    }
+    if selector >= variant_count {
+        return Err(SSZError::InvalidSelector {
+            selector: selector.into(),
+            reason: "Selector exceeds the number of union variants".into(),
+        });
     }
-}
 
-impl SimpleSerialize for MyUnion {
-    /// Serializes `MyUnion`.
-    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-        let start_len = buffer.len();
+    Ok((selector, &data[1..]))
+}
 
-        match self {
-            MyUnion::None => {
-                buffer.push(0);
+/// Declares an SSZ union and implements the SSZ traits for it.
+///
+/// Selectors are assigned in declaration order starting at `0`, so the first variant
+/// encodes as selector `0`, the second as `1`, and so on. A union may declare at most
+/// 128 variants (selectors `0..=127`); larger definitions fail to compile.
+///
+/// Variants either carry a single payload (`Large(u32)`), encoded as the selector followed by
+/// the payload, or none (`Empty`), encoded as the selector byte alone with a zero root. As in
+/// the spec's `Union[None, ...]`, only the first variant may be empty, and it can't be the
+/// only variant:
+///
+/// ```compile_fail
+/// r_ssz::ssz_union! {
+///     pub enum Misplaced {
+///         Small(u8),
+///         Empty,
+///     }
+/// }
+/// ```
+///
+/// ```
+/// use r_ssz::{SimpleSerialize, ssz_union};
+///
+/// ssz_union! {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Payload {
+///         Empty,
+///         Small(u8),
+///         Large(u32),
+///     }
+/// }
+///
+/// let mut buffer = vec![];
+/// Payload::Large(7).serialize(&mut buffer).unwrap();
+/// assert_eq!(buffer, vec![2, 7, 0, 0, 0]);
+///
+/// let mut buffer = vec![];
+/// Payload::Empty.serialize(&mut buffer).unwrap();
+/// assert_eq!(buffer, vec![0]);
+/// ```
+#[macro_export]
+macro_rules! ssz_union {
+    (@serialize $self:expr, $buffer:ident, $variant:ident ($ty:ty)) => {
+        match $self {
+            Self::$variant(value) => {
+                $crate::SimpleSerialize::serialize(value, $buffer)?;
             }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+    };
+    (@serialize $self:expr, $buffer:ident, $variant:ident) => {};
+
+    (@deserialize $payload:ident, $variant:ident ($ty:ty)) => {
+        Ok(Self::$variant(<$ty as $crate::SimpleDeserialize>::deserialize($payload)?))
+    };
+    (@deserialize $payload:ident, $variant:ident) => {
+        if $payload.is_empty() {
+            Ok(Self::$variant)
+        } else {
+            Err($crate::SSZError::InvalidByteLength {
+                expected: 0,
+                got: $payload.len(),
+            })
+        }
+    };
 
-            MyUnion::U32(val) => {
-                buffer.push(1);
-                val.serialize(buffer)?;
-            }
+    (@root $self:expr, $root:ident, $variant:ident ($ty:ty)) => {
+        match $self {
+            Self::$variant(value) => $root = $crate::Merkleize::hash_tree_root(value)?,
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+    };
+    (@root $self:expr, $root:ident, $variant:ident) => {};
+
+    (@is_unit $variant:ident ($ty:ty)) => {
+        false
+    };
+    (@is_unit $variant:ident) => {
+        true
+    };
+
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident $(($ty:ty))?),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant $(($ty))?),+
+        }
 
-            MyUnion::ByteList(vec) => {
-                buffer.push(2);
-                vec.serialize(buffer)?;
+        const _: () = assert!(
+            [$(stringify!($variant)),+].len() <= $crate::MAX_UNION_SELECTOR as usize + 1,
+            "SSZ unions support at most 128 variants"
+        );
+
+        const _: () = {
+            let unit = [$($crate::ssz_union!(@is_unit $variant $(($ty))?)),+];
+            let mut i = 1;
+            while i < unit.len() {
+                assert!(!unit[i], "only the first SSZ union variant may be empty");
+                i += 1;
+            }
+            assert!(
+                !unit[0] || unit.len() > 1,
+                "an SSZ union needs a variant besides the empty one"
+            );
+        };
+
+        impl $name {
+            /// Number of variants declared by this union.
+            pub const VARIANT_COUNT: u8 = [$(stringify!($variant)),+].len() as u8;
+
+            /// Returns the selector byte of this variant.
+            pub fn selector(&self) -> u8 {
+                #[repr(u8)]
+                enum Selector {
+                    $($variant),+
+                }
+                match self {
+                    $(Self::$variant { .. } => Selector::$variant as u8,)+
+                }
             }
         }
 
-        Ok(buffer.len() - start_len)
-    }
-}
+        impl $crate::SszTypeInfo for $name {
+            const IS_FIXED: bool = false;
 
-impl SimpleDeserialize for MyUnion {
-    /// Deserializes `MyUnion`.
-    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if data.is_empty() {
-            return Err(SSZError::ExpectedFurtherInput);
+            fn fixed_size() -> Option<usize> {
+                None
+            }
         }
 
-        let selector = data[0];
-        let payload = &data[1..];
+        impl $crate::SimpleSerialize for $name {
+            fn serialize(
+                &self,
+                buffer: &mut $crate::__private::Vec<u8>,
+            ) -> Result<usize, $crate::SSZError> {
+                let start_len = buffer.len();
+                buffer.push(self.selector());
+                $($crate::ssz_union!(@serialize self, buffer, $variant $(($ty))?);)+
+                Ok(buffer.len() - start_len)
+            }
+        }
 
-        match selector {
-            0 => {
-                if !payload.is_empty() {
-                    return Err(SSZError::InvalidByteLength {
-                        got: payload.len(),
-                        expected: 0,
-                    });
+        impl $crate::SimpleDeserialize for $name {
+            fn deserialize(data: &[u8]) -> Result<Self, $crate::SSZError> {
+                #[repr(u8)]
+                enum Selector {
+                    $($variant),+
                 }
-                Ok(MyUnion::None)
+                let (selector, payload) =
+                    $crate::union::deserialize_union(data, Self::VARIANT_COUNT)?;
+                $(
+                    if selector == Selector::$variant as u8 {
+                        return $crate::ssz_union!(@deserialize payload, $variant $(($ty))?);
+                    }
+                )+
+                unreachable!("selector is range-checked by `deserialize_union`")
             }
+        }
 
-            1 => {
-                let val = u32::deserialize(payload)?;
-                Ok(MyUnion::U32(val))
+        impl $crate::Merkleize for $name {
+            fn hash_tree_root(&self) -> Result<$crate::__private::B256, $crate::SSZError> {
+                #[allow(unused_mut)]
+                let mut root = $crate::__private::B256::ZERO;
+                $($crate::ssz_union!(@root self, root, $variant $(($ty))?);)+
+                Ok($crate::merkleization::mix_in_selector(
+                    root,
+                    self.selector() as usize,
+                ))
             }
 
-            2 => {
-                let vec = Vec::<u8>::deserialize(payload)?;
-                Ok(MyUnion::ByteList(vec))
+            fn mix_in_kind() -> $crate::MixInKind {
+                $crate::MixInKind::Selector
             }
-
-            sel if sel > 127 => Err(SSZError::InvalidSelector {
-                selector: sel.into(),
-                reason: "Selector value above 127 is reserved for forward compatibility".into(),
-            }),
-
-            sel => Err(SSZError::InvalidSelector {
-                selector: sel.into(),
-                reason: "Unknown selector".into(),
-            }),
         }
-    }
+    };
 }
 
-/// Implements `Merkleization` for `MyUnion`.
-impl Merkleize for MyUnion {
-    fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        match self {
-            MyUnion::None => Ok(mix_in_selector(B256::ZERO, 0)),
-            MyUnion::U32(val) => {
-                let root = val.hash_tree_root()?;
-                Ok(mix_in_selector(root, 1))
-            }
-            MyUnion::ByteList(vec) => {
-                let root = vec.hash_tree_root()?;
-                Ok(mix_in_selector(root, 2))
-            }
-        }
+crate::ssz_union! {
+    /// Basic union for serialization,deserialization and merkleization.
+    #[derive(Debug, PartialEq)]
+    pub enum MyUnion {
+        None,
+        U32(u32),
+        ByteList(Vec<u8>),
     }
 }
 
@@ -119,9 +231,7 @@ pub enum BadUnion {
 }
 
 impl SszTypeInfo for BadUnion {
-    fn is_fixed_size() -> bool {
-        false
-    }
+    const IS_FIXED: bool = false;
 
     fn fixed_size() -> Option<usize> {
         None
@@ -160,7 +270,7 @@ impl SimpleDeserialize for BadUnion {
         let selector = data[0];
         let payload = &data[1..];
         match selector {
-            sel if sel > 127 => Err(SSZError::InvalidSelector {
+            sel if sel > MAX_UNION_SELECTOR => Err(SSZError::InvalidSelector {
                 selector: sel.into(),
                 reason: "Selector above 127 is reserved".into(),
             }),
@@ -202,92 +312,21 @@ impl SimpleDeserialize for BadUnion {
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum Foo {
-    A(u32),
-    B(u8),
-}
-impl SszTypeInfo for Foo {
-    fn is_fixed_size() -> bool {
-        false
-    }
-
-    fn fixed_size() -> Option<usize> {
-        None
-    }
-}
-
-impl SimpleSerialize for Foo {
-    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-        let start_len = buffer.len();
-
-        match self {
-            Foo::A(val) => {
-                buffer.push(0);
-                val.serialize(buffer)?;
-            }
-            Foo::B(val) => {
-                buffer.push(1);
-                val.serialize(buffer)?;
-            }
-        }
-
-        Ok(buffer.len() - start_len)
-    }
-}
-
-impl SimpleDeserialize for Foo {
-    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if data.is_empty() {
-            return Err(SSZError::ExpectedFurtherInput);
-        }
-
-        let selector = data[0];
-        let payload = &data[1..];
-
-        match selector {
-            0 => {
-                let val = u32::deserialize(payload)?;
-                Ok(Foo::A(val))
-            }
-
-            1 => {
-                let val = u8::deserialize(payload)?;
-                Ok(Foo::B(val))
-            }
-
-            sel if sel > 127 => Err(SSZError::InvalidSelector {
-                selector: sel.into(),
-                reason: "Selector value above 127 is reserved for forward compatibility".into(),
-            }),
-
-            sel => Err(SSZError::InvalidSelector {
-                selector: sel.into(),
-                reason: "Unknown selector".into(),
-            }),
-        }
-    }
-}
-
-impl Merkleize for Foo {
-    fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        match self {
-            Foo::A(val) => {
-                let root = val.hash_tree_root()?;
-                Ok(mix_in_selector(root, 0))
-            }
-            Foo::B(val) => {
-                let root = val.hash_tree_root()?;
-                Ok(mix_in_selector(root, 1))
-            }
-        }
+crate::ssz_union! {
+    /// Union without an empty variant.
+    #[derive(Debug, PartialEq)]
+    pub enum Foo {
+        A(u32),
+        B(u8),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Merkleize, MixInKind, merkleization::mix_in_selector};
     use alloc::vec;
+    use alloy_primitives::B256;
 
     #[test]
     fn test_myunion_roundtrip_none() {
@@ -318,6 +357,20 @@ mod tests {
         original
             .serialize(&mut buffer)
             .expect("Serialization failed");
+        // The payload follows the selector directly; a union writes no offset for it.
+        assert_eq!(buffer, vec![2, 1, 2, 3, 4, 5]);
+        let decoded = MyUnion::deserialize(&buffer).expect("Deserialization failed");
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    fn test_myunion_roundtrip_empty_bytelist() {
+        let original = MyUnion::ByteList(vec![]);
+        let mut buffer = vec![];
+        original
+            .serialize(&mut buffer)
+            .expect("Serialization failed");
+        assert_eq!(buffer, vec![2]);
         let decoded = MyUnion::deserialize(&buffer).expect("Deserialization failed");
         assert_eq!(original, decoded);
     }
@@ -380,6 +433,176 @@ mod tests {
         assert_eq!(value_merkle.unwrap(), expected_root);
     }
 
+    #[test]
+    fn test_union_rejects_selector_beyond_variant_count() {
+        let result = Foo::deserialize(&[2u8, 6u8]);
+        match result {
+            Err(SSZError::InvalidSelector { selector, .. }) => assert_eq!(selector, 2),
+            other => panic!("Expected InvalidSelector error, got {other:?}"),
+        }
+
+        let result = MyUnion::deserialize(&[3u8]);
+        assert!(matches!(
+            result,
+            Err(SSZError::InvalidSelector { selector: 3, .. })
+        ));
+
+        assert_eq!(
+            Foo::deserialize(&[1u8, 6u8]).expect("valid selector"),
+            Foo::B(6)
+        );
+    }
+
+    #[test]
+    fn test_max_union_selector_is_rejected_above() {
+        assert_eq!(MAX_UNION_SELECTOR, 127);
+
+        let reserved = MAX_UNION_SELECTOR + 1;
+        assert!(matches!(
+            deserialize_union(&[reserved], u8::MAX),
+            Err(SSZError::InvalidSelector { selector: 128, .. })
+        ));
+        assert!(matches!(
+            BadUnion::deserialize(&[reserved, 0]),
+            Err(SSZError::InvalidSelector { selector: 128, .. })
+        ));
+        assert!(deserialize_union(&[MAX_UNION_SELECTOR], u8::MAX).is_ok());
+    }
+
+    crate::ssz_union! {
+        #[derive(Debug, PartialEq)]
+        enum Numbered {
+            First(u8),
+            Second(u16),
+            Third(Vec<u8>),
+        }
+    }
+
+    #[test]
+    fn test_ssz_union_assigns_selectors_in_order() {
+        assert_eq!(Numbered::VARIANT_COUNT, 3);
+        assert_eq!(Numbered::First(1).selector(), 0);
+        assert_eq!(Numbered::Second(1).selector(), 1);
+        assert_eq!(Numbered::Third(vec![]).selector(), 2);
+
+        let values = [
+            Numbered::First(7),
+            Numbered::Second(0x0102),
+            Numbered::Third(vec![1, 2, 3]),
+        ];
+        for value in values {
+            let mut buffer = vec![];
+            value.serialize(&mut buffer).expect("can serialize");
+            assert_eq!(buffer[0], value.selector());
+            let decoded = Numbered::deserialize(&buffer).expect("can deserialize");
+            assert_eq!(decoded, value);
+        }
+
+        let mut buffer = vec![];
+        Numbered::Second(0x0102)
+            .serialize(&mut buffer)
+            .expect("can serialize");
+        assert_eq!(buffer, vec![1, 0x02, 0x01]);
+
+        assert!(matches!(
+            Numbered::deserialize(&[3u8]),
+            Err(SSZError::InvalidSelector { selector: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_ssz_union_hash_tree_root() {
+        let value = Numbered::Second(6);
+        assert_eq!(
+            value.hash_tree_root().unwrap(),
+            mix_in_selector(6u16.hash_tree_root().unwrap(), 1)
+        );
+    }
+
+    crate::ssz_union! {
+        #[derive(Debug, PartialEq)]
+        enum WithUnit {
+            Nothing,
+            Number(u32),
+            Bytes(Vec<u8>),
+        }
+    }
+
+    #[test]
+    fn test_ssz_union_unit_variant() {
+        let mut buffer = vec![];
+        WithUnit::Nothing
+            .serialize(&mut buffer)
+            .expect("can serialize");
+        assert_eq!(buffer, vec![0]);
+
+        let values = [
+            WithUnit::Nothing,
+            WithUnit::Number(42),
+            WithUnit::Bytes(vec![1, 2, 3]),
+        ];
+        for value in values {
+            let mut buffer = vec![];
+            value.serialize(&mut buffer).expect("can serialize");
+            let decoded = WithUnit::deserialize(&buffer).expect("can deserialize");
+            assert_eq!(decoded, value);
+        }
+
+        assert_eq!(
+            WithUnit::deserialize(&[0, 1]),
+            Err(SSZError::InvalidByteLength {
+                expected: 0,
+                got: 1
+            })
+        );
+        assert_eq!(
+            WithUnit::Nothing.hash_tree_root().unwrap(),
+            mix_in_selector(B256::ZERO, 0)
+        );
+    }
+
+    crate::ssz_union! {
+        #[derive(Debug, PartialEq)]
+        enum Mixed {
+            Nothing,
+            Fixed(u32),
+            Variable(Vec<u16>),
+        }
+    }
+
+    #[test]
+    fn test_ssz_union_fixed_and_variable_payloads() {
+        // Both payloads follow the selector directly, without an offset.
+        let cases = [
+            (Mixed::Nothing, vec![0]),
+            (Mixed::Fixed(0x0102_0304), vec![1, 4, 3, 2, 1]),
+            (Mixed::Variable(vec![5, 6]), vec![2, 5, 0, 6, 0]),
+        ];
+        for (value, encoding) in cases {
+            let mut buffer = vec![];
+            value.serialize(&mut buffer).expect("can serialize");
+            assert_eq!(buffer, encoding);
+            assert_eq!(Mixed::deserialize(&buffer), Ok(value));
+        }
+
+        assert!(matches!(
+            Mixed::deserialize(&[128]),
+            Err(SSZError::InvalidSelector { selector: 128, .. })
+        ));
+        assert_eq!(
+            Mixed::Fixed(7).hash_tree_root().unwrap(),
+            mix_in_selector(7u32.hash_tree_root().unwrap(), 1)
+        );
+    }
+
+    #[test]
+    fn test_union_mix_in_kind() {
+        assert_eq!(MyUnion::mix_in_kind(), MixInKind::Selector);
+        assert_eq!(Numbered::mix_in_kind(), MixInKind::Selector);
+        assert_eq!(Option::<u64>::mix_in_kind(), MixInKind::Selector);
+        assert_eq!(u64::mix_in_kind(), MixInKind::None);
+    }
+
     #[test]
     fn check_union_hash_tree_root_2() {
         let original = MyUnion::None;
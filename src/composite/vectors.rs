@@ -12,15 +12,14 @@ use crate::{
 use alloc::vec;
 use alloc::vec::Vec;
 use alloy_primitives::B256;
+use core::ops::Range;
 
 impl<T> SszTypeInfo for Vec<T>
 where
     T: SszTypeInfo,
 {
-    ///Returns false since vectors are not primitive types.
-    fn is_fixed_size() -> bool {
-        false
-    }
+    ///Not fixed size since vectors are not primitive types.
+    const IS_FIXED: bool = false;
 
     ///Returns `None` since it is not of fixed size.
     fn fixed_size() -> Option<usize> {
@@ -30,6 +29,27 @@ where
 
 /// Implements serialization for vector.
 impl<T> SimpleSerialize for Vec<T>
+where
+    T: SimpleSerialize + SszTypeInfo,
+{
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        self.as_slice().serialize(buffer)
+    }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        self.as_slice().ssz_bytes_len()
+    }
+
+    #[cfg(feature = "std")]
+    fn serialize_to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<usize> {
+        self.as_slice().serialize_to_writer(writer)
+    }
+}
+
+/// Implements serialization for borrowed slices, same as `Vec<T>`.
+///
+/// Offsets for variable-size elements are based on the slice length.
+impl<T> SimpleSerialize for [T]
 where
     T: SimpleSerialize + SszTypeInfo,
 {
@@ -71,92 +91,159 @@ where
             }
         }
 
+        // Container serializers size their offsets with `ssz_bytes_len` before writing, so the
+        // two must agree.
+        debug_assert_eq!(Ok(buffer.len() - start_len), self.ssz_bytes_len());
         Ok(buffer.len() - start_len)
     }
+
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        match T::fixed_size() {
+            Some(size) if T::is_fixed_size() => Ok(self.len() * size),
+            _ => self
+                .iter()
+                .map(|item| Ok(crate::BYTES_PER_LENGTH_OFFSET + item.ssz_bytes_len()?))
+                .sum(),
+        }
+    }
+
+    /// Streams the elements, writing the offsets first from each element's `ssz_bytes_len`.
+    #[cfg(feature = "std")]
+    fn serialize_to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<usize> {
+        let mut written = 0;
+        if !T::is_fixed_size() {
+            let mut offset = self.len() * crate::BYTES_PER_LENGTH_OFFSET;
+            for item in self {
+                writer.write_all(&(offset as u32).to_le_bytes())?;
+                offset += item.ssz_bytes_len().map_err(std::io::Error::other)?;
+            }
+            written += self.len() * crate::BYTES_PER_LENGTH_OFFSET;
+        }
+        for item in self {
+            written += item.serialize_to_writer(writer)?;
+        }
+        Ok(written)
+    }
 }
 
-impl<T> SimpleDeserialize for Vec<T>
+/// Decodes an SSZ list of `T` into `buffer`, replacing its contents.
+///
+/// The buffer is cleared first and its allocation reused, so hot paths can decode many lists
+/// without reallocating. On error the buffer holds the elements decoded so far.
+pub fn deserialize_into<T>(buffer: &mut Vec<T>, data: &[u8]) -> Result<(), SSZError>
 where
     T: SimpleDeserialize + SszTypeInfo,
 {
-    /// Deserializes the vector.
-    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if T::is_fixed_size() {
-            let elem_size = T::fixed_size().ok_or(SSZError::InvalidLength {
-                expected: 0,
-                got: data.len(),
-            })?;
+    buffer.clear();
+    if T::is_fixed_size() {
+        let elem_size = T::fixed_size().ok_or(SSZError::InvalidLength {
+            expected: 0,
+            got: data.len(),
+        })?;
 
-            if data.len() % elem_size != 0 {
-                return Err(SSZError::InvalidLength {
-                    expected: elem_size,
-                    got: data.len(),
-                });
-            }
+        // Zero-size elements don't occupy any bytes, so the encoding can't say how many there
+        // are. SSZ disallows such types; reject them rather than divide by zero.
+        if elem_size == 0 {
+            return Err(SSZError::TypeMismatch {
+                context: "list of zero-size elements has no defined length".into(),
+            });
+        }
 
-            let count = data.len() / elem_size;
-            let mut result = Vec::with_capacity(count);
+        if !data.len().is_multiple_of(elem_size) {
+            return Err(SSZError::InvalidLength {
+                expected: elem_size,
+                got: data.len(),
+            });
+        }
 
-            for i in 0..count {
-                let start = i * elem_size;
-                let end = start + elem_size;
-                let elem_data = &data[start..end];
-                let elem = T::deserialize(elem_data)?;
-                result.push(elem);
-            }
+        // The total length is validated once above, so each element slice is exactly
+        // `elem_size` bytes and can skip the per-element length check.
+        buffer.reserve_exact(data.len() / elem_size);
+        for elem_data in data.chunks_exact(elem_size) {
+            buffer.push(T::deserialize_fixed(elem_data)?);
+        }
 
-            Ok(result)
-        } else {
-            const OFFSET_SIZE: usize = crate::BYTES_PER_LENGTH_OFFSET;
+        Ok(())
+    } else {
+        let ranges = variable_element_ranges(data)?;
+        buffer.reserve_exact(ranges.len());
+        for range in ranges {
+            buffer.push(T::deserialize(&data[range])?);
+        }
 
-            if data.len() < OFFSET_SIZE {
-                return Err(SSZError::InvalidLength {
-                    expected: OFFSET_SIZE,
-                    got: data.len(),
-                });
-            }
+        Ok(())
+    }
+}
 
-            let mut offsets = Vec::new();
-            let mut i = 0;
-            while i + OFFSET_SIZE <= data.len() {
-                let offset_bytes = &data[i..i + OFFSET_SIZE];
-                let offset = u32::from_le_bytes(offset_bytes.try_into().unwrap()) as usize;
-                if offset > data.len() {
-                    return Err(SSZError::OffsetOutOfBounds);
-                }
-                offsets.push(offset);
-                i += OFFSET_SIZE;
-
-                if i >= offsets[0] {
-                    break;
-                }
-            }
+/// Validates the offset table of an encoded list of variable-size elements and returns the byte
+/// range of each element within `data`.
+///
+/// Empty input is an empty list. Every offset must lie within `data` and offsets may not
+/// decrease; the last element runs to the end of `data`.
+pub(crate) fn variable_element_ranges(data: &[u8]) -> Result<Vec<Range<usize>>, SSZError> {
+    const OFFSET_SIZE: usize = crate::BYTES_PER_LENGTH_OFFSET;
 
-            let count = offsets.len();
-            let mut result = Vec::with_capacity(count);
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
 
-            for j in 0..count {
-                let start = offsets[j];
-                let end = if j + 1 < count {
-                    offsets[j + 1]
-                } else {
-                    data.len()
-                };
+    if data.len() < OFFSET_SIZE {
+        return Err(SSZError::InvalidLength {
+            expected: OFFSET_SIZE,
+            got: data.len(),
+        });
+    }
 
-                if start > end || end > data.len() {
-                    return Err(SSZError::InvalidOffsetRange { start, end });
-                }
+    // The first offset marks the end of the offset table, so it determines the element
+    // count. Reject it before reading any further offsets so a bogus value can't make us
+    // walk (or allocate for) more offsets than the buffer could possibly hold.
+    let first_offset = u32::from_le_bytes(data[..OFFSET_SIZE].try_into().unwrap()) as usize;
+    if first_offset == 0 || first_offset > data.len() || !first_offset.is_multiple_of(OFFSET_SIZE) {
+        return Err(SSZError::OffsetOutOfBounds);
+    }
+
+    let count = first_offset / OFFSET_SIZE;
+    let mut offsets = Vec::with_capacity(count + 1);
+    for i in 0..count {
+        let start = i * OFFSET_SIZE;
+        let offset_bytes = &data[start..start + OFFSET_SIZE];
+        let offset = u32::from_le_bytes(offset_bytes.try_into().unwrap()) as usize;
+        if offset > data.len() {
+            return Err(SSZError::OffsetOutOfBounds);
+        }
+        offsets.push(offset);
+    }
+    offsets.push(data.len());
 
-                let elem_data = &data[start..end];
-                let elem = T::deserialize(elem_data)?;
-                result.push(elem);
+    offsets
+        .windows(2)
+        .map(|pair| {
+            let (start, end) = (pair[0], pair[1]);
+            if start > end {
+                return Err(SSZError::InvalidOffsetRange { start, end });
             }
+            Ok(start..end)
+        })
+        .collect()
+}
 
-            Ok(result)
-        }
+impl<T> SimpleDeserialize for Vec<T>
+where
+    T: SimpleDeserialize + SszTypeInfo,
+{
+    /// Deserializes the vector.
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        let mut result = Vec::new();
+        deserialize_into(&mut result, data)?;
+        Ok(result)
     }
 }
 
+/// Merkleizes a `Vec<T>` as an SSZ `Vector[T, len]` of its current length.
+///
+/// An SSZ `Vector` root is the Merkle root of its chunks, while an SSZ `List` root additionally
+/// pads to the list limit and mixes in the length. A bare `Vec<T>` carries no limit, so it always
+/// has vector semantics; wrap list values in [`List<T, N>`](crate::ssz_list::List) instead.
 impl<T> Merkleize for Vec<T>
 where
     T: SszTypeInfo + SimpleSerialize + Merkleize,
@@ -180,11 +267,32 @@ where
                 .map(|element| element.hash_tree_root().map(|b256| b256.0))
                 .collect();
             let roots_bytes = roots?;
-            merkleize(&roots_bytes, Some(Self::chunk_count()))
+            let chunk_count = chunk_count(SSZType::VectorComposite { count: self.len() });
+            merkleize(&roots_bytes, Some(chunk_count))
         }
     }
 }
 
+/// Names the vector semantics of a `Vec<T>` root at the call site.
+///
+/// `Vec<T>` already merkleizes as an SSZ `Vector[T, len]` (see its `Merkleize` impl), so
+/// `hash_tree_root_as_vector` is the same root as `hash_tree_root`. It exists for code that
+/// wants to say so explicitly, next to list values that are wrapped in
+/// [`List<T, N>`](crate::ssz_list::List) for their length mix-in.
+pub trait MerkleizeVector {
+    /// Returns the vector `hash_tree_root`, without any length mix-in.
+    fn hash_tree_root_as_vector(&self) -> Result<B256, SSZError>;
+}
+
+impl<T> MerkleizeVector for Vec<T>
+where
+    T: SszTypeInfo + SimpleSerialize + Merkleize,
+{
+    fn hash_tree_root_as_vector(&self) -> Result<B256, SSZError> {
+        self.hash_tree_root()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -193,6 +301,17 @@ mod tests {
     use alloc::vec;
     use alloy_primitives::{B256, hex};
 
+    #[test]
+    fn test_is_fixed_const() {
+        const U64_FIXED: bool = <u64 as SszTypeInfo>::IS_FIXED;
+        const VEC_FIXED: bool = <Vec<u8> as SszTypeInfo>::IS_FIXED;
+        const ARRAY_FIXED: bool = <[u16; 4] as SszTypeInfo>::IS_FIXED;
+        const _: () = assert!(U64_FIXED && !VEC_FIXED && ARRAY_FIXED);
+
+        assert_eq!(u64::is_fixed_size(), U64_FIXED);
+        assert_eq!(Vec::<u8>::is_fixed_size(), VEC_FIXED);
+    }
+
     #[test]
     fn test_vec_fixed_size_serialization() {
         let v: Vec<u16> = vec![1, 2, 3, 4];
@@ -213,6 +332,154 @@ mod tests {
         assert_eq!(v, deserialized);
     }
 
+    #[test]
+    fn test_vec_variable_size_bogus_first_offset() {
+        let mut data = 4_000_000_000u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&[0u8; 8]);
+        assert_eq!(
+            Vec::<Vec<u8>>::deserialize(&data),
+            Err(SSZError::OffsetOutOfBounds)
+        );
+
+        // First offset inside the buffer but not on an offset boundary.
+        let mut data = 6u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&[0u8; 4]);
+        assert_eq!(
+            Vec::<Vec<u8>>::deserialize(&data),
+            Err(SSZError::OffsetOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_vec_zero_size_elements_rejected() {
+        #[derive(Debug, PartialEq)]
+        struct Unit;
+
+        impl SszTypeInfo for Unit {
+            const IS_FIXED: bool = true;
+
+            fn fixed_size() -> Option<usize> {
+                Some(0)
+            }
+        }
+
+        impl SimpleDeserialize for Unit {
+            fn deserialize(_data: &[u8]) -> Result<Self, SSZError> {
+                Ok(Unit)
+            }
+        }
+
+        for data in [&[][..], &[0u8; 4][..]] {
+            assert!(matches!(
+                Vec::<Unit>::deserialize(data),
+                Err(SSZError::TypeMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_vec_variable_size_extra_offset_region() {
+        // The first offset says the table holds two offsets, so a third offset-sized region
+        // is the first element's data, never a third offset.
+        let mut data = vec![];
+        for offset in [8u32, 12, 12] {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        assert_eq!(
+            Vec::<Vec<u8>>::deserialize(&data),
+            Ok(vec![vec![12, 0, 0, 0], vec![]])
+        );
+        assert_eq!(
+            Vec::<Vec<u64>>::deserialize(&data),
+            Err(SSZError::InvalidLength {
+                expected: 8,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_vec_variable_size_empty() {
+        let v: Vec<Vec<u8>> = Vec::new();
+        let mut buffer = vec![];
+        v.serialize(&mut buffer).expect("serialize empty vec");
+        assert!(buffer.is_empty());
+        let deserialized = Vec::<Vec<u8>>::deserialize(&buffer).expect("deserialize empty vec");
+        assert_eq!(v, deserialized);
+    }
+
+    #[test]
+    fn test_vec_variable_size_single_element() {
+        // A lone empty element: one offset pointing at the end of the buffer.
+        let data = 4u32.to_le_bytes();
+        assert_eq!(Vec::<Vec<u8>>::deserialize(&data), Ok(vec![vec![]]));
+
+        let v: Vec<Vec<u8>> = vec![vec![]];
+        let mut buffer = vec![];
+        v.serialize(&mut buffer)
+            .expect("serialize single empty element");
+        assert_eq!(buffer, data);
+
+        let v: Vec<Vec<u8>> = vec![vec![1, 2, 3]];
+        let mut buffer = vec![];
+        v.serialize(&mut buffer).expect("serialize single element");
+        assert_eq!(buffer, vec![4, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(Vec::<Vec<u8>>::deserialize(&buffer), Ok(v));
+    }
+
+    #[test]
+    fn test_vec_variable_size_offsets_and_trailing_bytes() {
+        // Offsets may not decrease.
+        let data = [8, 0, 0, 0, 6, 0, 0, 0, 1, 2];
+        assert_eq!(
+            Vec::<Vec<u8>>::deserialize(&data),
+            Err(SSZError::InvalidOffsetRange { start: 8, end: 6 })
+        );
+
+        // The last element always runs to the end of the input, so trailing bytes are handed
+        // to it and rejected by its own decoder when they don't fit its type.
+        let mut buffer = vec![];
+        vec![vec![1u16]].serialize(&mut buffer).unwrap();
+        buffer.push(0xff);
+        assert_eq!(
+            Vec::<Vec<u16>>::deserialize(&buffer),
+            Err(SSZError::InvalidLength {
+                expected: 2,
+                got: 3
+            })
+        );
+
+        // For a byte list the extra bytes are indistinguishable from element data.
+        assert_eq!(
+            Vec::<Vec<u8>>::deserialize(&[4, 0, 0, 0, 1, 0, 0xff]),
+            Ok(vec![vec![1, 0, 0xff]])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_into_reuses_buffer() {
+        let mut encoded = vec![];
+        vec![1u64, 2, 3].serialize(&mut encoded).unwrap();
+
+        let mut buffer: Vec<u64> = Vec::with_capacity(16);
+        let ptr = buffer.as_ptr();
+        deserialize_into(&mut buffer, &encoded).expect("can deserialize");
+        assert_eq!(buffer, vec![1, 2, 3]);
+
+        let mut encoded = vec![];
+        vec![4u64, 5].serialize(&mut encoded).unwrap();
+        deserialize_into(&mut buffer, &encoded).expect("can deserialize");
+        assert_eq!(buffer, vec![4, 5]);
+        assert_eq!(buffer.capacity(), 16);
+        assert_eq!(buffer.as_ptr(), ptr);
+
+        let mut nested: Vec<Vec<u8>> = vec![vec![9; 4]; 3];
+        let mut encoded = vec![];
+        vec![vec![1u8], vec![]].serialize(&mut encoded).unwrap();
+        deserialize_into(&mut nested, &encoded).expect("can deserialize");
+        assert_eq!(nested, vec![vec![1], vec![]]);
+    }
+
     #[test]
     fn test_vec_empty() {
         let v: Vec<u8> = Vec::new();
@@ -232,4 +499,96 @@ mod tests {
         ));
         assert_eq!(root, expected_root, "Hash tree root mismatch for basic vec");
     }
+
+    #[test]
+    fn test_slice_serialize_matches_vec() {
+        use crate::container::Foo;
+
+        let foos = vec![Foo { a: 1, b: 2 }, Foo { a: 3, b: 4 }, Foo { a: 5, b: 6 }];
+        let mut owned = vec![];
+        foos.serialize(&mut owned).expect("can serialize");
+        let mut borrowed = vec![];
+        let slice: &[Foo] = &foos[1..];
+        let written = slice.serialize(&mut borrowed).expect("can serialize");
+        assert_eq!(written, 10);
+        assert_eq!(borrowed, owned[5..]);
+
+        let parts: Vec<Vec<u8>> = vec![vec![1, 2], vec![], vec![3]];
+        let mut owned = vec![];
+        parts.serialize(&mut owned).expect("can serialize");
+        let mut borrowed = vec![];
+        parts
+            .as_slice()
+            .serialize(&mut borrowed)
+            .expect("can serialize");
+        assert_eq!(borrowed, owned);
+
+        // The offset base follows the slice length, not the backing vector's.
+        let mut tail = vec![];
+        parts[1..].serialize(&mut tail).expect("can serialize");
+        assert_eq!(tail, vec![8, 0, 0, 0, 8, 0, 0, 0, 3]);
+        assert_eq!(parts[1..].ssz_bytes_len(), Ok(tail.len()));
+    }
+
+    #[test]
+    fn test_vec_of_bitlists() {
+        use crate::BitList;
+
+        let lists: Vec<BitList<16>> = vec![
+            BitList::default(),
+            BitList::from_bools(&[true, false, true, true, false]).unwrap(),
+            BitList::from_bools(&[true; 8]).unwrap(),
+            BitList::from_bools(&[false; 16]).unwrap(),
+        ];
+
+        let mut buffer = vec![];
+        lists.serialize(&mut buffer).expect("can serialize");
+        // Each inner bitlist keeps its delimiter inside its own offset-delimited slice.
+        let mut expected = vec![];
+        for offset in [16u32, 17, 18, 20] {
+            expected.extend_from_slice(&offset.to_le_bytes());
+        }
+        expected.extend_from_slice(&[0x01, 0b0010_1101, 0xff, 0x01, 0x00, 0x00, 0x01]);
+        assert_eq!(buffer, expected);
+
+        let decoded = Vec::<BitList<16>>::deserialize(&buffer).expect("can deserialize");
+        assert_eq!(decoded, lists);
+
+        let roots: Vec<[u8; 32]> = lists
+            .iter()
+            .map(|list| list.hash_tree_root().unwrap().0)
+            .collect();
+        let root = lists.hash_tree_root().expect("can merkleize");
+        assert_eq!(root, merkleize(&roots, None).unwrap());
+    }
+
+    #[test]
+    fn test_vec_hash_tree_root_is_vector_root() {
+        use crate::fixed_vectors::FixedVector;
+        use crate::ssz_list::List;
+
+        let v: Vec<u16> = vec![1, 2, 3, 4];
+        let root = v.hash_tree_root().expect("can merkleize");
+        assert_eq!(
+            root,
+            FixedVector::new([1u16, 2, 3, 4]).hash_tree_root().unwrap()
+        );
+        assert_ne!(
+            root,
+            List::<u16, 4>::new(v.clone())
+                .unwrap()
+                .hash_tree_root()
+                .unwrap()
+        );
+
+        let nested: Vec<Vec<u8>> = vec![vec![1], vec![2, 3], vec![4]];
+        let expected = FixedVector::new([vec![1u8], vec![2, 3], vec![4]])
+            .hash_tree_root()
+            .unwrap();
+        assert_eq!(nested.hash_tree_root().unwrap(), expected);
+
+        // The explicit alias gives the same vector roots.
+        assert_eq!(v.hash_tree_root_as_vector(), Ok(root));
+        assert_eq!(nested.hash_tree_root_as_vector(), Ok(expected));
+    }
 }
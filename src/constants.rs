@@ -6,5 +6,9 @@ pub const BYTES_PER_CHUNK: usize = 32;
 pub const BYTES_PER_LENGTH_OFFSET: usize = 4;
 /// Number of bits per byte.
 pub const BITS_PER_BYTE: usize = 8;
+/// Highest union selector; larger selectors are reserved for forward compatibility.
+pub const MAX_UNION_SELECTOR: u8 = 127;
 /// Bytes per U256.
 pub const BYTES: usize = 32;
+/// Number of context bytes (e.g. a fork digest) prefixed by `encode_with_context`.
+pub const CONTEXT_BYTES_LEN: usize = 4;
@@ -1,58 +1,56 @@
 //! Contains serialization,deserialization and merkleization for `Profile[MyStableContainer]`
 
-use crate::{Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, merkleization::merkleize};
-use alloc::vec::Vec;
+use crate::{
+    Merkleize, MixInKind, MyStableContainer, SSZError, SimpleDeserialize, SimpleSerialize,
+    SszTypeInfo,
+};
 use alloy_primitives::B256;
 
-#[derive(Debug, Clone, PartialEq)]
+/// Serialized like a regular container of its fields, which the derives provide.
+#[derive(Debug, Clone, PartialEq, Default, SimpleSerialize, SimpleDeserialize, SszTypeInfo)]
 pub struct MyProfile {
     pub a: u32,
     pub b: bool,
 }
 
-/// Serializes `MyProfile` as per Eip-7495 specs
-impl SimpleSerialize for MyProfile {
-    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-        let mut local_buffer = Vec::new();
-        self.a.serialize(&mut local_buffer)?;
-        self.b.serialize(&mut local_buffer)?;
-        buffer.extend_from_slice(&local_buffer);
-        Ok(buffer.len())
-    }
-}
-
-/// Deserializes `MyProfile` as per Eip-7495 specs
-impl SimpleDeserialize for MyProfile {
-    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if data.len() < 5 {
-            return Err(SSZError::InvalidByteLength {
-                got: data.len(),
-                expected: 5,
-            });
+/// Views the profile as its base container, with `c` inactive.
+impl From<MyProfile> for MyStableContainer {
+    fn from(profile: MyProfile) -> Self {
+        Self {
+            a: Some(profile.a),
+            b: Some(profile.b),
+            c: None,
         }
-
-        let a = u32::deserialize(&data[0..4])?;
-        let b = bool::deserialize(&data[4..5])?;
-        Ok(Self { a, b })
     }
 }
 
 /// Merkleizes `MyProfile` as per Eip-7495 specs
+///
+/// A profile shares the root of its base `MyStableContainer` with the same fields active.
 impl Merkleize for MyProfile {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        let a_hash = self.a.hash_tree_root()?;
-        let b_hash = self.b.hash_tree_root()?;
-        merkleize(&[a_hash.into(), b_hash.into()], None)
+        MyStableContainer::from(self.clone()).hash_tree_root()
     }
 
     fn chunk_count() -> usize {
-        2
+        MyStableContainer::chunk_count()
+    }
+
+    fn merkle_depth() -> u32 {
+        MyStableContainer::merkle_depth()
+    }
+
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Aux
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{MyProfile, SimpleDeserialize, SimpleSerialize};
+    use crate::{
+        Merkleize, MyProfile, MyStableContainer, SimpleDeserialize, SimpleSerialize,
+        ssz::assert_fixed_size_correct,
+    };
     use alloc::vec::Vec;
 
     #[test]
@@ -62,6 +60,11 @@ mod tests {
         let mut buffer = Vec::new();
         let bytes_written = container.serialize(&mut buffer).unwrap();
         assert_eq!(bytes_written, 5);
+        assert_eq!(buffer, [42, 0, 0, 0, 1]);
+
+        // Only the bytes written by this call are counted.
+        let mut prefixed = alloc::vec![0xff];
+        assert_eq!(container.serialize(&mut prefixed), Ok(5));
 
         let deserialized = MyProfile::deserialize(&buffer).unwrap();
         assert_eq!(container, deserialized);
@@ -78,4 +81,40 @@ mod tests {
         let deserialized = MyProfile::deserialize(&buffer).unwrap();
         assert_eq!(container, deserialized);
     }
+
+    #[test]
+    fn test_fixed_size_matches_serialization() {
+        assert_fixed_size_correct::<MyProfile>();
+    }
+
+    #[test]
+    fn test_deserialize_rejects_wrong_length() {
+        // 4 bytes is short and 6 bytes is long for the 5-byte profile.
+        assert!(MyProfile::deserialize(&[42, 0, 0, 0]).is_err());
+        assert!(MyProfile::deserialize(&[42, 0, 0, 0, 1, 0]).is_err());
+    }
+
+    #[test]
+    fn test_hash_tree_root_matches_stable_container() {
+        let profile = MyProfile { a: 42, b: true };
+        let stable = MyStableContainer {
+            a: Some(42),
+            b: Some(true),
+            c: None,
+        };
+        assert_eq!(
+            profile.hash_tree_root().unwrap(),
+            stable.hash_tree_root().unwrap()
+        );
+
+        // Activating a field the profile leaves out changes the root.
+        let wider = MyStableContainer {
+            c: Some(0),
+            ..stable
+        };
+        assert_ne!(
+            profile.hash_tree_root().unwrap(),
+            wider.hash_tree_root().unwrap()
+        );
+    }
 }
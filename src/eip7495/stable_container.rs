@@ -1,14 +1,84 @@
 //! Contains serialization,deserialization and merkleization for `Container[N]`
 
+use crate::container::{serialize_fields, split_fields};
 use crate::ssz::SszTypeInfo;
-use crate::{BITS_PER_BYTE, BYTES_PER_LENGTH_OFFSET};
 use crate::{
-    BitVector, Merkleize, SSZError, SimpleDeserialize, SimpleSerialize,
-    merkleization::{merkleize, mix_in_aux},
+    BitVector, Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize,
+    merkleization::{get_depth, merkleize, mix_in_aux},
 };
 use alloc::vec::Vec;
 use alloy_primitives::B256;
 
+/// Serializes the fields of a `StableContainer[N]`, `None` for each absent field.
+///
+/// The active fields bitvector comes first, then the present fields laid out like a
+/// container's by [`serialize_fields`]. Each present field is paired with whether its type is
+/// fixed-size.
+pub fn serialize_stable_fields<'a, const N: usize, I>(
+    fields: I,
+    buffer: &mut Vec<u8>,
+) -> Result<usize, SSZError>
+where
+    I: IntoIterator<Item = Option<(&'a dyn SimpleSerialize, bool)>>,
+    I::IntoIter: Clone,
+{
+    let fields = fields.into_iter();
+    let active: Vec<bool> = fields.clone().map(|field| field.is_some()).collect();
+    let written = BitVector::<N>::from_bools(&active)?.serialize(buffer)?;
+    Ok(written + serialize_fields(fields.flatten(), buffer)?)
+}
+
+/// Splits an encoded `StableContainer[N]` into the encodings of its fields, `None` for each
+/// absent field.
+///
+/// `field_sizes` holds each field's fixed size, or `None` for a variable-size field. Bits set
+/// beyond the declared fields are rejected, and the present fields are split with
+/// [`split_fields`].
+pub fn split_stable_fields<'a, const N: usize>(
+    data: &'a [u8],
+    field_sizes: &[Option<usize>],
+) -> Result<Vec<Option<&'a [u8]>>, SSZError> {
+    let bitvector_len = N.div_ceil(8);
+    let bitvector = BitVector::<N>::deserialize(
+        data.get(..bitvector_len)
+            .ok_or(SSZError::ExpectedFurtherInput)?,
+    )?;
+    if (field_sizes.len()..N).any(|i| bitvector.get(i).unwrap_or(false)) {
+        return Err(SSZError::InvalidBitvector);
+    }
+
+    let active = |i: usize| bitvector.get(i).unwrap_or(false);
+    let active_sizes: Vec<Option<usize>> = field_sizes
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| active(i))
+        .map(|(_, &size)| size)
+        .collect();
+    let mut present = split_fields(&data[bitvector_len..], &active_sizes)?.into_iter();
+    Ok((0..field_sizes.len())
+        .map(|i| active(i).then(|| present.next().expect("one encoding per present field")))
+        .collect())
+}
+
+/// Merkleizes a `StableContainer[N]` from the roots of its fields, `None` for each absent field.
+///
+/// Absent fields merkleize as zero chunks in a tree of `N` leaves, and the root of the active
+/// fields bitvector is mixed in.
+pub fn stable_container_root<const N: usize>(
+    field_roots: &[Option<B256>],
+) -> Result<B256, SSZError> {
+    let active: Vec<bool> = field_roots.iter().map(Option::is_some).collect();
+    let chunks: Vec<[u8; 32]> = field_roots
+        .iter()
+        .map(|root| root.unwrap_or_default().0)
+        .collect();
+    let data_root = merkleize(&chunks, Some(N))?;
+    Ok(mix_in_aux(
+        data_root,
+        BitVector::<N>::from_bools(&active)?.hash_tree_root()?,
+    ))
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct MyStableContainer {
     pub a: Option<u32>,
@@ -18,188 +88,103 @@ pub struct MyStableContainer {
 
 pub const N: usize = 4;
 
-/// Serializes `MyStableContainer` as per Eip-7495 specs
-impl SimpleSerialize for MyStableContainer {
-    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-        // Create the bitvector
-        let mut active_flags_vec =
-            alloc::vec![self.a.is_some(), self.b.is_some(), self.c.is_some()];
-
-        active_flags_vec.resize(N, false);
-
-        let active_flags: [bool; N] = active_flags_vec.try_into().unwrap();
-        let bitvector = BitVector::<N>::from_bools(&active_flags[..])?;
-        // serialize the bitvector to be appended first
-        let _ = bitvector.serialize(buffer)?;
-
-        // Collect active values
-        let active_values: Vec<(Vec<u8>, bool)> = {
-            let mut temp = Vec::new();
-
-            if let Some(a) = self.a {
-                let mut buf = Vec::new();
-                a.serialize(&mut buf)?;
-                temp.push((buf, u32::is_fixed_size()));
-            }
-
-            if let Some(b) = self.b {
-                let mut buf = Vec::new();
-                b.serialize(&mut buf)?;
-                temp.push((buf, bool::is_fixed_size()));
-            }
-
-            if let Some(c) = self.c {
-                let mut buf = Vec::new();
-                c.serialize(&mut buf)?;
-                temp.push((buf, u64::is_fixed_size()));
-            }
-
-            temp
-        };
-
-        // Separate fixed-size and variable-size parts
-        let mut fixed_lengths = Vec::new();
-        let mut fixed_parts = Vec::new();
-        let mut variable_parts = Vec::new();
-
-        for (buf, is_fixed) in &active_values {
-            if *is_fixed {
-                fixed_parts.push(Some(buf.clone()));
-                fixed_lengths.push(buf.len());
-            } else {
-                fixed_parts.push(None);
-                fixed_lengths.push(BYTES_PER_LENGTH_OFFSET);
-                variable_parts.push(buf.clone());
-            }
-        }
-
-        // Verify total size
-        let variable_lengths: Vec<usize> = variable_parts.iter().map(|v| v.len()).collect();
-        let total_len: usize =
-            fixed_lengths.iter().sum::<usize>() + variable_lengths.iter().sum::<usize>();
-
-        if total_len as u64 >= 1u64 << (BYTES_PER_LENGTH_OFFSET * BITS_PER_BYTE) {
-            return Err(SSZError::OffsetOutOfBounds);
-        }
-
-        // Compute and serialize offsets
-        let mut variable_offsets = Vec::new();
-        let mut offset = fixed_lengths.iter().sum::<usize>();
+impl MyStableContainer {
+    /// Returns the indices of the fields that are present (`Some`).
+    pub fn active_fields(&self) -> Vec<usize> {
+        [self.a.is_some(), self.b.is_some(), self.c.is_some()]
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &active)| active.then_some(i))
+            .collect()
+    }
 
-        for var_len in &variable_lengths {
-            let mut offset_buf = Vec::new();
-            u32::try_from(offset).unwrap().serialize(&mut offset_buf)?;
-            variable_offsets.push(offset_buf);
-            offset += var_len;
+    /// Checks whether `data` is a canonical encoding without materializing the container.
+    ///
+    /// The reserved bits of the active fields bitvector must be zero and the present fields must
+    /// end exactly at the end of the input. All fields of `MyStableContainer` are fixed-size, so
+    /// there are no offsets whose ordering needs checking.
+    pub fn is_canonical(data: &[u8]) -> Result<bool, SSZError> {
+        const BITVECTOR_LEN: usize = N.div_ceil(8);
+        if data.len() < BITVECTOR_LEN {
+            return Err(SSZError::InvalidLength {
+                expected: BITVECTOR_LEN,
+                got: data.len(),
+            });
         }
 
-        // Append fixed parts (offsets interleaved where needed)
-        let mut var_offset_index = 0;
-        for part in fixed_parts {
-            if let Some(data) = part {
-                buffer.extend_from_slice(&data);
-            } else {
-                buffer.extend_from_slice(&variable_offsets[var_offset_index]);
-                var_offset_index += 1;
-            }
+        let bitvector = BitVector::<N>::deserialize(&data[..BITVECTOR_LEN])?;
+        let field_sizes = [u32::fixed_size(), bool::fixed_size(), u64::fixed_size()];
+        if (field_sizes.len()..N).any(|i| bitvector.get(i).unwrap_or(false)) {
+            return Ok(false);
         }
 
-        // Append variable parts
-        for var_part in variable_parts {
-            buffer.extend_from_slice(&var_part);
-        }
+        let fields_len: usize = field_sizes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| bitvector.get(*i).unwrap_or(false))
+            .map(|(_, size)| size.unwrap_or(0))
+            .sum();
+        Ok(data.len() == BITVECTOR_LEN + fields_len)
+    }
+}
 
-        Ok(buffer.len())
+/// Serializes `MyStableContainer` as per Eip-7495 specs
+impl SimpleSerialize for MyStableContainer {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        serialize_stable_fields::<N, _>(
+            [
+                self.a
+                    .as_ref()
+                    .map(|a| (a as &dyn SimpleSerialize, u32::IS_FIXED)),
+                self.b
+                    .as_ref()
+                    .map(|b| (b as &dyn SimpleSerialize, bool::IS_FIXED)),
+                self.c
+                    .as_ref()
+                    .map(|c| (c as &dyn SimpleSerialize, u64::IS_FIXED)),
+            ],
+            buffer,
+        )
     }
 }
 
 /// Deserializes `MyStableContainer` as per Eip-7495 specs
 impl SimpleDeserialize for MyStableContainer {
     fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        const NUM_FIELDS: usize = 3;
-        const BITVECTOR_LEN: usize = N.div_ceil(8);
-        // Step 1: Deserialize bitvector and validate extra bits
-        let mut cursor = 0;
-        let bitvector = {
-            let bv = BitVector::<N>::deserialize(&data[cursor..cursor + BITVECTOR_LEN])?;
-            cursor += BITVECTOR_LEN;
-
-            // Validate unused bits beyond NUM_FIELDS are false
-            for i in NUM_FIELDS..N {
-                if bv.get(i).unwrap_or(false) {
-                    return Err(SSZError::InvalidBitvector);
-                }
-            }
-
-            bv
-        };
-        // Step 2: Deserialize fixed-size fields based on presence
-        let mut a = None;
-        let mut b = None;
-        let mut c = None;
-
-        if bitvector.get(0).unwrap_or(false) {
-            a = Some(u32::deserialize(&data[cursor..cursor + 4])?);
-            cursor += 4;
-        }
-        if bitvector.get(1).unwrap_or(false) {
-            b = Some(bool::deserialize(&data[cursor..cursor + 1])?);
-            cursor += 1;
-        }
-        if bitvector.get(2).unwrap_or(false) {
-            c = Some(u64::deserialize(&data[cursor..cursor + 8])?);
-        }
-
-        Ok(Self { a, b, c })
+        let fields = split_stable_fields::<N>(
+            data,
+            &[u32::fixed_size(), bool::fixed_size(), u64::fixed_size()],
+        )?;
+        Ok(Self {
+            a: fields[0].map(u32::deserialize).transpose()?,
+            b: fields[1].map(bool::deserialize).transpose()?,
+            c: fields[2].map(u64::deserialize).transpose()?,
+        })
     }
 }
 
 /// Merkelizes `MyStableContainer` as per Eip-7495 specs
 impl Merkleize for MyStableContainer {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        // Step 1: hash each field or use default
-        let a_hash = match self.a {
-            Some(x) => x.hash_tree_root(),
-            None => Ok(B256::ZERO),
-        };
-        let b_hash = match self.b {
-            Some(x) => x.hash_tree_root(),
-            None => Ok(B256::ZERO),
-        };
-        let c_hash = match self.c {
-            Some(x) => x.hash_tree_root(),
-            None => Ok(B256::ZERO),
-        };
-
-        let field_hashes = alloc::vec![a_hash, b_hash, c_hash];
-        let hashes: Vec<[u8; 32]> = field_hashes
-            .into_iter()
-            .map(|res| res.unwrap().into())
-            .collect();
-        // Step 2: compute merkle root of fields
-        let merkle_root = merkleize(&hashes, None);
-
-        // Step 3: construct active fields bitvector
-        let mut bits = BitVector::<3>::default();
-        if self.a.is_some() {
-            bits.set(0, true).unwrap();
-        }
-        if self.b.is_some() {
-            bits.set(1, true).unwrap();
-        }
-        if self.c.is_some() {
-            bits.set(2, true).unwrap();
-        }
-
-        let active_root = bits.hash_tree_root();
-
-        // Step 4: mix the auxiliary
-        Ok(mix_in_aux(merkle_root?, active_root?))
+        stable_container_root::<N>(&[
+            self.a.as_ref().map(Merkleize::hash_tree_root).transpose()?,
+            self.b.as_ref().map(Merkleize::hash_tree_root).transpose()?,
+            self.c.as_ref().map(Merkleize::hash_tree_root).transpose()?,
+        ])
     }
 
     fn chunk_count() -> usize {
         3
     }
+
+    /// Adds the active fields mix-in layer.
+    fn merkle_depth() -> u32 {
+        get_depth(N) + 1
+    }
+
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Aux
+    }
 }
 
 #[cfg(test)]
@@ -273,4 +258,117 @@ mod tests {
         let deserialized = MyStableContainer::deserialize(&buffer).unwrap();
         assert_eq!(deserialized, container);
     }
+
+    #[test]
+    fn test_serialize_into_non_empty_buffer() {
+        let container = MyStableContainer {
+            a: Some(7),
+            b: Some(true),
+            c: None,
+        };
+
+        let mut buffer = alloc::vec![0xff];
+        let written = container.serialize(&mut buffer).unwrap();
+        assert_eq!(written, 6);
+        assert_eq!(buffer, alloc::vec![0xff, 0b0011, 7, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        let container = MyStableContainer {
+            a: Some(123),
+            b: None,
+            c: Some(456),
+        };
+        let mut buffer = Vec::new();
+        container.serialize(&mut buffer).unwrap();
+        assert_eq!(MyStableContainer::is_canonical(&buffer), Ok(true));
+
+        // Reserved bit 3 set in the active fields bitvector.
+        let mut reserved = buffer.clone();
+        reserved[0] |= 0b1000;
+        assert_eq!(MyStableContainer::is_canonical(&reserved), Ok(false));
+
+        // Trailing byte after the last present field.
+        let mut trailing = buffer.clone();
+        trailing.push(0);
+        assert_eq!(MyStableContainer::is_canonical(&trailing), Ok(false));
+
+        // Truncated field data.
+        assert_eq!(
+            MyStableContainer::is_canonical(&buffer[..buffer.len() - 1]),
+            Ok(false)
+        );
+
+        assert!(MyStableContainer::is_canonical(&[]).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rejects_malformed_input() {
+        // Reserved bit 3 set in the active fields bitvector.
+        assert_eq!(
+            MyStableContainer::deserialize(&[0b1000]),
+            Err(SSZError::InvalidBitvector)
+        );
+        // `a` is active but its 4 bytes are missing.
+        assert_eq!(
+            MyStableContainer::deserialize(&[0b0001, 7, 0]),
+            Err(SSZError::ExpectedFurtherInput)
+        );
+        assert_eq!(
+            MyStableContainer::deserialize(&[]),
+            Err(SSZError::ExpectedFurtherInput)
+        );
+        // Trailing byte after the last present field.
+        assert_eq!(
+            MyStableContainer::deserialize(&[0b0010, 1, 0]),
+            Err(SSZError::InvalidByteLength {
+                expected: 1,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_stable_fields_with_variable_field() {
+        let bytes: Vec<u8> = alloc::vec![1, 2];
+        let mut buffer = Vec::new();
+        let written = serialize_stable_fields::<8, _>(
+            [
+                Some((&7u16 as &dyn SimpleSerialize, true)),
+                None,
+                Some((&bytes as &dyn SimpleSerialize, false)),
+            ],
+            &mut buffer,
+        )
+        .unwrap();
+        assert_eq!(buffer, [0b101, 7, 0, 6, 0, 0, 0, 1, 2]);
+        assert_eq!(written, buffer.len());
+
+        let fields = split_stable_fields::<8>(&buffer, &[Some(2), Some(1), None]).unwrap();
+        assert_eq!(fields, [Some(&[7u8, 0][..]), None, Some(&[1u8, 2][..])]);
+    }
+
+    #[test]
+    fn test_merkle_depth() {
+        assert_eq!(MyStableContainer::merkle_depth(), 3);
+        assert_eq!(MyStableContainer::mix_in_kind(), MixInKind::Aux);
+    }
+
+    #[test]
+    fn test_active_fields() {
+        let container = MyStableContainer {
+            a: Some(123),
+            b: None,
+            c: Some(456),
+        };
+        assert_eq!(container.active_fields(), alloc::vec![0, 2]);
+
+        let empty = MyStableContainer {
+            a: None,
+            b: None,
+            c: None,
+        };
+        assert!(empty.active_fields().is_empty());
+    }
 }
@@ -3,11 +3,11 @@
 /// See: <https://eips.ethereum.org/EIPS/eip-7495>
 ///
 use crate::ssz::SszTypeInfo;
+use crate::ssz_list::List;
 use crate::{
-    BYTES_PER_LENGTH_OFFSET, BitVector, Merkleize, SSZError, SimpleSerialize,
-    merkleization::{merkleize, mix_in_aux},
+    Merkleize, SSZError, SimpleSerialize, merkleization::get_depth, serialize_stable_fields,
+    stable_container_root,
 };
-use alloc::vec;
 use alloc::vec::Vec;
 use alloy_primitives::B256;
 
@@ -31,253 +31,46 @@ pub struct Shape2 {
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Shape3 {
     pub side: Option<u16>,
-    pub colors: Option<[u8; 2]>,
+    pub colors: Option<List<u8, 4>>,
     pub radius: Option<u16>,
 }
 
-macro_rules! impl_stable_container_3 {
+macro_rules! impl_stable_container {
     ($name:ident, $n:expr, $($field:ident : $typ:ty),+$(,)?) => {
-         impl SimpleSerialize for $name {
+        impl SimpleSerialize for $name {
             fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-                let mut active_flags_vec = vec![];
-                let mut active_fields: Vec<(&str, bool)> = vec![];
-                $(
-                    let is_some = self.$field.is_some();
-                    active_flags_vec.push(is_some);
-                    active_fields.push((stringify!($field), is_some));
-                )+
-                active_flags_vec.resize($n, false);
-                let bitvector = BitVector::<$n>::from_bools(&active_flags_vec)?;
-                // Collect active field values
-                let mut fixed_parts: Vec<Option<Vec<u8>>> = vec![];
-                let mut variable_parts: Vec<Vec<u8>> = vec![];
-
-                $(
-                    if let Some(val) = &self.$field {
-                        let mut ser = vec![];
-                        val.serialize(&mut ser)?;
-
-                        if !<$typ>::is_fixed_size() {
-                            fixed_parts.push(None);
-                            variable_parts.push(ser);
-                        } else {
-                            fixed_parts.push(Some(ser));
-                        }
-                    }
-                )+
-
-                // Compute fixed_lengths
-                let mut fixed_lengths = vec![];
-                for part in &fixed_parts {
-                    if let Some(p) = part {
-                        fixed_lengths.push(p.len());
-                    } else {
-                        fixed_lengths.push(BYTES_PER_LENGTH_OFFSET);
-                    }
-                }
-
-                // Compute variable_offsets
-                let mut variable_offsets = vec![];
-                let mut current_offset = fixed_lengths.iter().sum::<usize>();
-                for part in &variable_parts {
-                    let mut offset_buf = vec![];
-                    (current_offset as u32).serialize(&mut offset_buf)?;
-                    variable_offsets.push(offset_buf);
-                    current_offset += part.len();
-                }
-
-                // Replace None in fixed_parts with variable_offsets
-                let mut offset_idx = 0;
-                let final_fixed_parts: Vec<Vec<u8>> = fixed_parts
-                    .into_iter()
-                    .map(|p| {
-                        if let Some(v) = p {
-                            v
-                        } else {
-                            let v = variable_offsets[offset_idx].clone();
-                            offset_idx += 1;
-                            v
-                        }
-                    })
-                    .collect();
-
-                // Final serialization: bitvector + fixed + variable
-                let mut full_serialized = vec![];
-                bitvector.serialize(&mut full_serialized)?;
-                for part in final_fixed_parts {
-                    full_serialized.extend(part);
-                }
-                for part in variable_parts {
-                    full_serialized.extend(part);
-                }
-
-                buffer.extend(full_serialized);
-                Ok(buffer.len())
+                serialize_stable_fields::<$n, _>(
+                    [$(self
+                        .$field
+                        .as_ref()
+                        .map(|value| (value as &dyn SimpleSerialize, <$typ>::IS_FIXED))),+],
+                    buffer,
+                )
             }
         }
 
-impl Merkleize for $name {
-    fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        const N: usize = $n;
-
-        // 1. Hash each field, or zero if None
-        let mut field_hashes: Vec<B256> = vec![B256::ZERO; N];
-        let mut active_bits = BitVector::<N>::default();
-
-        let mut idx = 0;
-        $(
-            if let Some(value) = &self.$field {
-                field_hashes[idx] = value.hash_tree_root()?;
-                active_bits.set(idx, true).unwrap();
+        impl Merkleize for $name {
+            fn hash_tree_root(&self) -> Result<B256, SSZError> {
+                stable_container_root::<$n>(&[
+                    $(self.$field.as_ref().map(Merkleize::hash_tree_root).transpose()?),+
+                ])
             }
-            idx += 1;
-        )+
-
-        // 2. Convert field_hashes to [u8; 32] form
-        let hashes: Vec<[u8; 32]> = field_hashes.into_iter().map(Into::into).collect();
-
-        // 3. Merkleize and mix in BitVector root
-        let data_root = merkleize(&hashes, None)?;
-        let bits_root = active_bits.hash_tree_root()?;
-
-        Ok(mix_in_aux(data_root, bits_root))
-    }
-
-    fn chunk_count() -> usize {
-        $n
-    }
-}
-
-    };
-}
-
-impl_stable_container_3!(Shape1, N1, side: u16, color: u8, radius: u16);
-impl_stable_container_3!(Shape2, N2, side: u16, color: u8, radius: u16);
-
-impl SimpleSerialize for Shape3 {
-    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
-        const N: usize = 8;
-
-        // Bitvector for 3 fields, padded to 8
-        let mut active_flags = vec![
-            self.side.is_some(),
-            self.colors.is_some(),
-            self.radius.is_some(),
-        ];
-        active_flags.resize(N, false);
-        let bitvector = BitVector::<N>::from_bools(&active_flags)?;
-
-        // Active fields (order matters!)
-        let mut fixed_parts: Vec<Option<Vec<u8>>> = vec![];
-        let mut variable_parts: Vec<Vec<u8>> = vec![];
-
-        if let Some(val) = self.side {
-            let mut buf = vec![];
-            val.serialize(&mut buf)?;
-            fixed_parts.push(Some(buf));
-        }
-
-        if let Some(val) = self.colors {
-            let mut buf = vec![];
-            val.serialize(&mut buf)?;
-            fixed_parts.push(None); // variable-size placeholder
-            variable_parts.push(buf);
-        }
-
-        if let Some(val) = self.radius {
-            let mut buf = vec![];
-            val.serialize(&mut buf)?;
-            fixed_parts.push(Some(buf));
-        }
-
-        // Compute fixed lengths
-        let fixed_lengths: Vec<usize> = fixed_parts
-            .iter()
-            .map(|p| p.as_ref().map_or(BYTES_PER_LENGTH_OFFSET, |v| v.len()))
-            .collect();
 
-        // Compute variable offsets (relative to post-bitvector fields)
-        let mut variable_offsets = vec![];
-        let mut current_offset = fixed_lengths.iter().sum::<usize>();
-        for part in &variable_parts {
-            let mut buf = vec![];
-            (current_offset as u32).serialize(&mut buf)?;
-            variable_offsets.push(buf);
-            current_offset += part.len();
-        }
-
-        // Finalize fixed section with variable offsets
-        let mut offset_idx = 0;
-        let final_fixed_parts: Vec<Vec<u8>> = fixed_parts
-            .into_iter()
-            .map(|part| {
-                if let Some(bytes) = part {
-                    bytes
-                } else {
-                    let v = variable_offsets[offset_idx].clone();
-                    offset_idx += 1;
-                    v
-                }
-            })
-            .collect();
+            fn chunk_count() -> usize {
+                $n
+            }
 
-        // Compose final buffer
-        let mut result = vec![];
-        bitvector.serialize(&mut result)?;
-        for part in final_fixed_parts {
-            result.extend(part);
-        }
-        for part in variable_parts {
-            result.extend(part);
+            /// Adds the active fields mix-in layer.
+            fn merkle_depth() -> u32 {
+                get_depth($n) + 1
+            }
         }
-        buffer.extend(result);
-        Ok(buffer.len())
-    }
+    };
 }
 
-impl Merkleize for Shape3 {
-    fn hash_tree_root(&self) -> Result<B256, SSZError> {
-        const N: usize = 8;
-        let mut chunks: Vec<[u8; 32]> = vec![];
-
-        chunks.push(match &self.side {
-            Some(x) => x.hash_tree_root()?.into(),
-            None => [0u8; 32],
-        });
-
-        chunks.push(match &self.colors {
-            Some(x) => x.hash_tree_root()?.into(),
-            None => [0u8; 32],
-        });
-
-        chunks.push(match &self.radius {
-            Some(x) => x.hash_tree_root()?.into(),
-            None => [0u8; 32],
-        });
-        for _ in 3..N {
-            chunks.push([0u8; 32]);
-        }
-        let merkle_root = merkleize(&chunks, None);
-
-        let mut bits = BitVector::<N>::default();
-        if self.side.is_some() {
-            bits.set(0, true)?;
-        }
-        if self.colors.is_some() {
-            bits.set(1, true)?;
-        }
-        if self.radius.is_some() {
-            bits.set(2, true)?;
-        }
-        let active_root = bits.hash_tree_root()?;
-
-        Ok(mix_in_aux(merkle_root?, active_root))
-    }
-
-    fn chunk_count() -> usize {
-        8
-    }
-}
+impl_stable_container!(Shape1, N1, side: u16, color: u8, radius: u16);
+impl_stable_container!(Shape2, N2, side: u16, color: u8, radius: u16);
+impl_stable_container!(Shape3, N2, side: u16, colors: List<u8, 4>, radius: u16);
 
 #[cfg(test)]
 mod tests {
@@ -288,6 +81,13 @@ mod tests {
         B256::from_slice(&hex::decode(hex).expect("Invalid hex string"))
     }
 
+    #[test]
+    fn test_stable_container_merkle_depth() {
+        assert_eq!(Shape1::merkle_depth(), 3);
+        assert_eq!(Shape2::merkle_depth(), 4);
+        assert_eq!(Shape3::merkle_depth(), 4);
+    }
+
     #[test]
     fn test_shape1_cases() {
         let test_cases = [
@@ -391,7 +191,7 @@ mod tests {
             (
                 Shape3 {
                     side: Some(0x42),
-                    colors: Some([1, 2]),
+                    colors: Some(List::new(alloc::vec![1, 2]).unwrap()),
                     radius: Some(0x42),
                 },
                 "0742000800000042000102",
@@ -409,7 +209,7 @@ mod tests {
             (
                 Shape3 {
                     side: None,
-                    colors: Some([1, 2]),
+                    colors: Some(List::new(alloc::vec![1, 2]).unwrap()),
                     radius: None,
                 },
                 "02040000000102",
@@ -427,7 +227,7 @@ mod tests {
             (
                 Shape3 {
                     side: None,
-                    colors: Some([1, 2]),
+                    colors: Some(List::new(alloc::vec![1, 2]).unwrap()),
                     radius: Some(0x42),
                 },
                 "060600000042000102",
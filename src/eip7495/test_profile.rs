@@ -2,12 +2,14 @@
 ///
 /// See: <https://eips.ethereum.org/EIPS/eip-7495>
 ///
-use crate::{Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, merkleization::merkleize};
+use crate::{
+    Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo, merkleization::merkleize,
+};
 use alloc::vec::Vec;
 use alloy_primitives::B256;
 
 ///NOTE: Here Square -> Profile[MyProfile]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Square {
     pub side: u16,
     pub color: u8,
@@ -27,7 +29,7 @@ impl SimpleSerialize for Square {
 /// Deserializes Square as per Eip-7495 specs
 impl SimpleDeserialize for Square {
     fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if data.len() < 3 {
+        if data.len() != 3 {
             return Err(SSZError::InvalidByteLength {
                 got: data.len(),
                 expected: 3,
@@ -40,6 +42,15 @@ impl SimpleDeserialize for Square {
     }
 }
 
+/// `SszTypeInfo` for Square.
+impl SszTypeInfo for Square {
+    const IS_FIXED: bool = true;
+
+    fn fixed_size() -> Option<usize> {
+        Some(3)
+    }
+}
+
 /// Merkleizes Square as per Eip-7495 specs
 impl Merkleize for Square {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
@@ -54,7 +65,7 @@ impl Merkleize for Square {
 }
 
 ///NOTE: Here Circle -> Profile[MyProfile]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Circle {
     pub color: u8,
     pub radius: u16,
@@ -74,7 +85,7 @@ impl SimpleSerialize for Circle {
 /// Deserializes Circle as per Eip-7495 specs
 impl SimpleDeserialize for Circle {
     fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
-        if data.len() < 3 {
+        if data.len() != 3 {
             return Err(SSZError::InvalidByteLength {
                 got: data.len(),
                 expected: 3,
@@ -87,6 +98,15 @@ impl SimpleDeserialize for Circle {
     }
 }
 
+/// `SszTypeInfo` for Circle.
+impl SszTypeInfo for Circle {
+    const IS_FIXED: bool = true;
+
+    fn fixed_size() -> Option<usize> {
+        Some(3)
+    }
+}
+
 /// Merkleizes Circle as per Eip-7495 specs
 impl Merkleize for Circle {
     fn hash_tree_root(&self) -> Result<B256, SSZError> {
@@ -102,7 +122,10 @@ impl Merkleize for Circle {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Circle, SimpleDeserialize, SimpleSerialize, Square};
+    use crate::{
+        Circle, SSZError, SimpleDeserialize, SimpleSerialize, Square,
+        ssz::assert_fixed_size_correct,
+    };
     use alloc::vec::Vec;
     use alloy_primitives::hex;
 
@@ -141,4 +164,25 @@ mod tests {
         let deserialized = Circle::deserialize(&buffer).unwrap();
         assert_eq!(container, deserialized);
     }
+
+    #[test]
+    fn test_fixed_size_matches_serialization() {
+        assert_fixed_size_correct::<Square>();
+        assert_fixed_size_correct::<Circle>();
+    }
+
+    #[test]
+    fn test_deserialize_rejects_wrong_length() {
+        // Square and Circle are both exactly 3 bytes.
+        for data in [&[0x42, 0x00][..], &[0x42, 0x00, 0x01, 0x00]] {
+            assert_eq!(
+                Square::deserialize(data),
+                Err(SSZError::InvalidByteLength {
+                    expected: 3,
+                    got: data.len()
+                })
+            );
+            assert!(Circle::deserialize(data).is_err());
+        }
+    }
 }
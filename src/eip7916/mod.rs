@@ -2,3 +2,6 @@
 
 pub mod progressive_list;
 pub use progressive_list::*;
+
+pub mod progressive_container;
+pub use progressive_container::*;
@@ -0,0 +1,202 @@
+//! Implements the serialization and merkleization of progressive containers
+///
+/// See: <https://eips.ethereum.org/EIPS/eip-7916>
+///
+use alloc::{format, vec::Vec};
+use alloy_primitives::B256;
+
+use crate::{
+    Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
+    container::serialize_fields,
+    merkleization::{BYTES_PER_CHUNK, merkleize_progressive_list, mix_in_aux},
+};
+
+/// Maximum number of field positions a progressive container may declare.
+pub const MAX_PROGRESSIVE_FIELDS: usize = 256;
+
+/// Merkleizes a progressive container from the roots of its fields, in declaration order.
+///
+/// `active_fields` lays the fields out over the progressive tree: each `true` position holds the
+/// next field root and each `false` position a zero chunk, so fields keep their generalized
+/// index when positions are retired or appended. The layout must end with an active position,
+/// span at most [`MAX_PROGRESSIVE_FIELDS`] positions and hold exactly one position per field.
+/// The packed layout is mixed into the root.
+pub fn merkleize_progressive_container(
+    field_roots: &[B256],
+    active_fields: &[bool],
+) -> Result<B256, SSZError> {
+    if active_fields.len() > MAX_PROGRESSIVE_FIELDS {
+        return Err(SSZError::InvalidActiveFields {
+            reason: format!(
+                "{} positions exceed the maximum of {MAX_PROGRESSIVE_FIELDS}",
+                active_fields.len()
+            ),
+        });
+    }
+    if active_fields.last() != Some(&true) {
+        return Err(SSZError::InvalidActiveFields {
+            reason: "layout must end with an active position".into(),
+        });
+    }
+    let active_count = active_fields.iter().filter(|&&active| active).count();
+    if active_count != field_roots.len() {
+        return Err(SSZError::InvalidLength {
+            expected: active_count,
+            got: field_roots.len(),
+        });
+    }
+
+    let mut roots = field_roots.iter();
+    let chunks: Vec<[u8; BYTES_PER_CHUNK]> = active_fields
+        .iter()
+        .map(|&active| match active {
+            true => roots.next().expect("one root per active field").0,
+            false => [0u8; BYTES_PER_CHUNK],
+        })
+        .collect();
+
+    let mut layout = [0u8; BYTES_PER_CHUNK];
+    for (i, _) in active_fields
+        .iter()
+        .enumerate()
+        .filter(|(_, active)| **active)
+    {
+        layout[i / 8] |= 1 << (i % 8);
+    }
+
+    let root = merkleize_progressive_list(&chunks, 1, 4)?;
+    Ok(mix_in_aux(root, B256::from(layout)))
+}
+
+/// Example progressive container whose middle field position has been retired.
+///
+/// `ProgressiveContainer(active_fields=[1, 0, 1])` with fields `side: uint16, color: uint8`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProgressiveSquare {
+    pub side: u16,
+    pub color: u8,
+}
+
+impl ProgressiveSquare {
+    pub const ACTIVE_FIELDS: [bool; 3] = [true, false, true];
+}
+
+/// Serializes `ProgressiveSquare` like a regular container of its fields
+impl SimpleSerialize for ProgressiveSquare {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError> {
+        serialize_fields(
+            [
+                (&self.side as &dyn SimpleSerialize, u16::IS_FIXED),
+                (&self.color as &dyn SimpleSerialize, u8::IS_FIXED),
+            ],
+            buffer,
+        )
+    }
+}
+
+/// Deserializes `ProgressiveSquare` like a regular container of its fields
+impl SimpleDeserialize for ProgressiveSquare {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        if data.len() != 3 {
+            return Err(SSZError::InvalidByteLength {
+                expected: 3,
+                got: data.len(),
+            });
+        }
+
+        let side = u16::deserialize(&data[0..2])?;
+        let color = u8::deserialize(&data[2..3])?;
+        Ok(Self { side, color })
+    }
+}
+
+impl SszTypeInfo for ProgressiveSquare {
+    const IS_FIXED: bool = true;
+
+    fn fixed_size() -> Option<usize> {
+        Some(3)
+    }
+}
+
+/// Merkleizes `ProgressiveSquare` over the progressive tree
+impl Merkleize for ProgressiveSquare {
+    fn hash_tree_root(&self) -> Result<B256, SSZError> {
+        merkleize_progressive_container(
+            &[self.side.hash_tree_root()?, self.color.hash_tree_root()?],
+            &Self::ACTIVE_FIELDS,
+        )
+    }
+
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Aux
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_progressive_square_roundtrip() {
+        let square = ProgressiveSquare {
+            side: 0x42,
+            color: 1,
+        };
+        let mut buffer = Vec::new();
+        square.serialize(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0x42, 0x00, 0x01]);
+        assert_eq!(ProgressiveSquare::deserialize(&buffer).unwrap(), square);
+        crate::ssz::assert_fixed_size_correct::<ProgressiveSquare>();
+    }
+
+    #[test]
+    fn test_progressive_square_hash_tree_root() {
+        let square = ProgressiveSquare {
+            side: 0x42,
+            color: 1,
+        };
+        let side = square.side.hash_tree_root().unwrap();
+        let color = square.color.hash_tree_root().unwrap();
+
+        // The retired middle position stays a zero chunk in the field layer.
+        let field_root =
+            merkleize_progressive_list(&[side.0, [0u8; BYTES_PER_CHUNK], color.0], 1, 4).unwrap();
+        let mut layout = [0u8; BYTES_PER_CHUNK];
+        layout[0] = 0b101;
+        let expected = mix_in_aux(field_root, B256::from(layout));
+        assert_eq!(square.hash_tree_root().unwrap(), expected);
+        assert_eq!(
+            expected,
+            B256::from(hex!(
+                "3188a4dadd7e2674b7d2581705c095a34401f074e64d890c44628c8fbef42848"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_progressive_container_rejects_bad_layout() {
+        let root = B256::repeat_byte(1);
+        let layout_error = |result: Result<B256, SSZError>| {
+            matches!(result, Err(SSZError::InvalidActiveFields { .. }))
+        };
+        assert!(layout_error(merkleize_progressive_container(&[root], &[])));
+        assert!(layout_error(merkleize_progressive_container(
+            &[root],
+            &[true, false]
+        )));
+        assert!(layout_error(merkleize_progressive_container(
+            &[],
+            &[false; MAX_PROGRESSIVE_FIELDS + 1]
+        )));
+        assert_eq!(
+            merkleize_progressive_container(&[root], &[true, true]),
+            Err(SSZError::InvalidLength {
+                expected: 2,
+                got: 1
+            })
+        );
+        assert!(merkleize_progressive_container(&[root; 2], &[true; 2]).is_ok());
+    }
+}
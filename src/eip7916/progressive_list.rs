@@ -6,7 +6,7 @@ use alloc::vec::Vec;
 use alloy_primitives::B256;
 
 use crate::{
-    Merkleize, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
+    Merkleize, MixInKind, SSZError, SimpleDeserialize, SimpleSerialize, SszTypeInfo,
     merkleization::{merkleize_progressive_list, mix_in_length, pack},
 };
 
@@ -74,6 +74,10 @@ where
         let root = merkleize_progressive_list(&chunks, 1, 4)?;
         Ok(mix_in_length(root, self.len()))
     }
+
+    fn mix_in_kind() -> MixInKind {
+        MixInKind::Length
+    }
 }
 
 #[cfg(test)]
@@ -160,4 +164,16 @@ mod tests {
 
         assert_ne!(root1, root2); // different lengths should produce different roots
     }
+
+    #[test]
+    fn test_hash_tree_root_reference() {
+        // Reference root from the EIP-7916 pseudocode: one packed chunk, then the length.
+        let list = ProgressiveList::new(vec![1u8, 2, 3]);
+        assert_eq!(
+            list.hash_tree_root().unwrap(),
+            B256::from(alloy_primitives::hex!(
+                "5cbec8baa86d8cfcb4e5f7af2013172c60c31a1263cd8ca4b6df7ed15e4a40a7"
+            ))
+        );
+    }
 }
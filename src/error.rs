@@ -29,6 +29,9 @@ pub enum SSZError {
     #[error("Offset out of bounds for data length")]
     OffsetOutOfBounds,
 
+    #[error("Index {index} out of bounds for length {len}")]
+    IndexOutOfBounds { index: usize, len: usize },
+
     #[error("Invalid offset range: start {start} is greater than end {end}")]
     InvalidOffsetRange { start: usize, end: usize },
 
@@ -47,9 +50,33 @@ pub enum SSZError {
     #[error("{reason} for {selector}")]
     InvalidSelector { reason: String, selector: usize },
 
+    #[error("Invalid generalized index: {index}")]
+    InvalidGeneralizedIndex { index: u64 },
+
+    #[error("Unknown field: {name}")]
+    UnknownField { name: String },
+
+    #[error("Proofs disagree on the node at generalized index {index}")]
+    InconsistentProofs { index: u64 },
+
+    #[error("Input too large: limit {limit} bytes, got {got}")]
+    InputTooLarge { limit: usize, got: usize },
+
+    #[error("Type mismatch: {context}")]
+    TypeMismatch { context: String },
+
     #[error("Invalid bitvector")]
     InvalidBitvector,
 
+    #[error("Invalid progressive tree: base size {base_size}, scale {scale}")]
+    InvalidProgressiveShape { base_size: usize, scale: usize },
+
+    #[error("Progressive tree level {level} holds more chunks than fit in usize")]
+    ProgressiveLevelOverflow { level: usize },
+
+    #[error("Invalid active fields: {reason}")]
+    InvalidActiveFields { reason: String },
+
     #[error("Unknown error occurred")]
     Unknown,
 }
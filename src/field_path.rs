@@ -0,0 +1,86 @@
+//! Generalized indices of named fields, for building proofs by field path.
+
+use crate::{SSZError, merkleization::get_generalized_index};
+use alloc::string::ToString;
+use alloy_primitives::U256;
+
+/// The `FieldPath` trait resolves a path of field names to a node of the type's Merkle tree.
+pub trait FieldPath {
+    /// Returns the generalized index of the node at `path`, relative to this type's root.
+    ///
+    /// An empty path resolves to the root itself, `1`.
+    fn generalized_index(path: &[&str]) -> Result<u64, SSZError>;
+}
+
+/// Resolves `path` in a container whose fields are `field_names`, in declaration order.
+///
+/// The first name in `path` selects a field; `child` resolves the rest of the path within the
+/// field at the given position.
+pub fn container_generalized_index(
+    field_names: &[&str],
+    path: &[&str],
+    child: impl FnOnce(usize, &[&str]) -> Result<u64, SSZError>,
+) -> Result<u64, SSZError> {
+    let Some((name, rest)) = path.split_first() else {
+        return Ok(1);
+    };
+    let position = field_names
+        .iter()
+        .position(|field| field == name)
+        .ok_or_else(|| SSZError::UnknownField {
+            name: name.to_string(),
+        })?;
+
+    let field = get_generalized_index(field_names.len(), position);
+    Ok(concat_generalized_indices(&[field, child(position, rest)?]))
+}
+
+/// Combines generalized indices, each relative to the node the previous one points at, into one
+/// index relative to the outermost root, as in the consensus spec.
+pub fn concat_generalized_indices(indices: &[u64]) -> u64 {
+    indices.iter().fold(1, |acc, &index| {
+        let depth = index.ilog2();
+        (acc << depth) | (index ^ (1 << depth))
+    })
+}
+
+macro_rules! impl_leaf_field_path {
+    ($($type:ty),+) => {
+        $(
+            impl FieldPath for $type {
+                fn generalized_index(path: &[&str]) -> Result<u64, SSZError> {
+                    match path.first() {
+                        None => Ok(1),
+                        Some(name) => Err(SSZError::UnknownField {
+                            name: name.to_string(),
+                        }),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_leaf_field_path!(bool, u8, u16, u32, u64, u128, U256);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concat_generalized_indices() {
+        assert_eq!(concat_generalized_indices(&[]), 1);
+        assert_eq!(concat_generalized_indices(&[1, 5]), 5);
+        assert_eq!(concat_generalized_indices(&[3, 3]), 7);
+        assert_eq!(concat_generalized_indices(&[2, 5, 1]), 9);
+    }
+
+    #[test]
+    fn test_leaf_field_path() {
+        assert_eq!(u64::generalized_index(&[]), Ok(1));
+        assert!(matches!(
+            u64::generalized_index(&["a"]),
+            Err(SSZError::UnknownField { .. })
+        ));
+    }
+}
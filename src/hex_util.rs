@@ -0,0 +1,19 @@
+//! Internal hex helpers backing the `from_hex`/`to_hex` convenience methods.
+
+use crate::{SSZError, SimpleDeserialize, SimpleSerialize};
+use alloc::{format, string::String, vec::Vec};
+use alloy_primitives::hex;
+
+/// Decodes a hex string (with or without `0x` prefix) and deserializes it as `T`.
+pub(crate) fn from_hex<T: SimpleDeserialize>(s: &str) -> Result<T, SSZError> {
+    let bytes =
+        hex::decode(s).map_err(|e| SSZError::DeserializationError(format!("invalid hex: {e}")))?;
+    T::deserialize(&bytes)
+}
+
+/// Serializes `value` and encodes it as a `0x`-prefixed hex string.
+pub(crate) fn to_hex<T: SimpleSerialize>(value: &T) -> Result<String, SSZError> {
+    let mut buffer = Vec::new();
+    value.serialize(&mut buffer)?;
+    Ok(hex::encode_prefixed(buffer))
+}
@@ -2,6 +2,12 @@
 #![no_std]
 #![allow(unused_assignments)]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+// Lets the derive macros' `::r_ssz` paths resolve inside this crate too.
+extern crate self as r_ssz;
+
+pub use r_ssz_derive::{Merkleize, SimpleDeserialize, SimpleSerialize, SszTypeInfo};
 
 pub mod basic;
 pub use basic::*;
@@ -20,8 +26,30 @@ pub use constants::*;
 
 pub mod merkleization;
 
+pub mod schema;
+pub use schema::*;
+
+pub mod field_path;
+pub use field_path::*;
+
+mod hex_util;
+
 pub mod eip7495;
 pub use eip7495::*;
 
 pub mod eip7916;
 pub use eip7916::*;
+
+#[cfg(feature = "reflect")]
+pub mod reflect;
+#[cfg(feature = "reflect")]
+pub use reflect::*;
+
+/// Re-exports used by the crate's macros. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+    pub use alloy_primitives::B256;
+    #[cfg(feature = "std")]
+    pub use std::io;
+}
@@ -1,13 +1,53 @@
 //! SSZ Merkleization helper functions.
 
-use crate::SSZError;
-use alloc::vec::Vec;
+use crate::{Merkleize, SSZError, SimpleSerialize, SszTypeInfo};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 use alloy_primitives::B256;
 use sha2::{Digest, Sha256};
 
 pub const BYTES_PER_CHUNK: usize = 32;
 
+/// Hash function that combines two sibling Merkle tree nodes into their parent.
+///
+/// [`Sha256Hasher`] is the one the SSZ spec uses and the default everywhere; the `_with`
+/// variants of [`merkleize`] and the mix-in helpers accept any other implementation.
+pub trait MerkleHasher {
+    fn hash_pair(
+        left: &[u8; BYTES_PER_CHUNK],
+        right: &[u8; BYTES_PER_CHUNK],
+    ) -> [u8; BYTES_PER_CHUNK];
+}
+
+/// SHA-256 over the concatenation of both nodes, as the SSZ spec defines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha256Hasher;
+
+impl MerkleHasher for Sha256Hasher {
+    fn hash_pair(
+        left: &[u8; BYTES_PER_CHUNK],
+        right: &[u8; BYTES_PER_CHUNK],
+    ) -> [u8; BYTES_PER_CHUNK] {
+        let mut hasher = Sha256::new();
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().into()
+    }
+}
+
 /// Returns the next power of two >= i. 0 → 1
+///
+/// This is the number of leaves a Merkle tree over `i` chunks is padded to.
+///
+/// ```
+/// use r_ssz::merkleization::next_pow_of_two;
+///
+/// assert_eq!(next_pow_of_two(0), 1);
+/// assert_eq!(next_pow_of_two(5), 8);
+/// assert_eq!(next_pow_of_two(8), 8);
+/// ```
 pub fn next_pow_of_two(i: usize) -> usize {
     if i == 0 {
         1
@@ -16,6 +56,58 @@ pub fn next_pow_of_two(i: usize) -> usize {
     }
 }
 
+/// Returns the depth of a Merkle tree holding `chunk_count` chunks, i.e. `log2` of the padded leaf count.
+///
+/// ```
+/// use r_ssz::merkleization::get_depth;
+///
+/// assert_eq!(get_depth(1), 0);
+/// assert_eq!(get_depth(5), 3);
+/// assert_eq!(get_depth(8), 3);
+/// ```
+pub fn get_depth(chunk_count: usize) -> u32 {
+    next_pow_of_two(chunk_count).trailing_zeros()
+}
+
+/// Returns the generalized index of chunk `position` in a tree merkleized over `chunk_count`
+/// chunks, as `merkleize` pads it.
+///
+/// `position` must lie within the padded width, `next_pow_of_two(chunk_count)`; this is
+/// checked in debug builds.
+///
+/// ```
+/// use r_ssz::merkleization::get_generalized_index;
+///
+/// // A container with 3 fields pads to 4 leaves, at indices 4..8.
+/// assert_eq!(get_generalized_index(3, 0), 4);
+/// assert_eq!(get_generalized_index(3, 2), 6);
+/// ```
+pub fn get_generalized_index(chunk_count: usize, position: usize) -> u64 {
+    let width = next_pow_of_two(chunk_count);
+    debug_assert!(
+        position < width,
+        "chunk {position} is outside a tree of {width} leaves"
+    );
+    (width + position) as u64
+}
+
+/// Returns the generalized index of chunk `position` in a tree whose root has a value mixed in
+/// (`mix_in_length`, `mix_in_selector` or `mix_in_aux`), which puts the chunk tree one level
+/// down as the root's left child.
+///
+/// `position` must lie within the padded width, as for [`get_generalized_index`].
+///
+/// ```
+/// use r_ssz::merkleization::get_generalized_index_mixed_in;
+///
+/// // `List[uint64, 8]` packs into 2 chunks; element 5 is in chunk 1.
+/// assert_eq!(get_generalized_index_mixed_in(2, 1), 5);
+/// ```
+pub fn get_generalized_index_mixed_in(chunk_count: usize, position: usize) -> u64 {
+    // Same depth as the unmixed index, plus one level for the mix-in.
+    get_generalized_index(chunk_count, position) + next_pow_of_two(chunk_count) as u64
+}
+
 /// Returns the number of 32-byte chunks required for merkleization.
 pub fn chunk_count(ty: SSZType) -> usize {
     match ty {
@@ -32,13 +124,26 @@ pub fn chunk_count(ty: SSZType) -> usize {
 
 /// Mixes in the auxiliary root (like a bitvector) into the Merkle root.
 pub fn mix_in_aux(root: B256, aux: B256) -> B256 {
-    let mut hasher = Sha256::new();
-    hasher.update(root);
-    hasher.update(aux);
-    B256::from_slice(&hasher.finalize())
+    mix_in_aux_with::<Sha256Hasher>(root, aux)
+}
+
+/// [`mix_in_aux`] with a custom hasher.
+pub fn mix_in_aux_with<H: MerkleHasher>(root: B256, aux: B256) -> B256 {
+    B256::from(H::hash_pair(&root.0, &aux.0))
 }
 
 /// Packs serialized basic values into 32-byte chunks with right-padding.
+///
+/// ```
+/// use r_ssz::merkleization::pack;
+///
+/// let chunks = pack(&[1, 2, 3]);
+/// assert_eq!(chunks.len(), 1);
+/// assert_eq!(chunks[0][..3], [1, 2, 3]);
+/// assert!(chunks[0][3..].iter().all(|&b| b == 0));
+///
+/// assert!(pack(&[]).is_empty());
+/// ```
 pub fn pack(bytes: &[u8]) -> Vec<[u8; BYTES_PER_CHUNK]> {
     let mut out = Vec::new();
     for chunk in bytes.chunks(BYTES_PER_CHUNK) {
@@ -49,6 +154,29 @@ pub fn pack(bytes: &[u8]) -> Vec<[u8; BYTES_PER_CHUNK]> {
     out
 }
 
+/// Right-pads `bytes` with zeros to a single 32-byte chunk.
+///
+/// Fails if `bytes` is longer than a chunk rather than truncating it.
+///
+/// ```
+/// use r_ssz::merkleization::pad_to_chunk;
+///
+/// let chunk = pad_to_chunk(&[1, 2, 3]).unwrap();
+/// assert_eq!(chunk[..3], [1, 2, 3]);
+/// assert!(pad_to_chunk(&[0; 33]).is_err());
+/// ```
+pub fn pad_to_chunk(bytes: &[u8]) -> Result<[u8; BYTES_PER_CHUNK], SSZError> {
+    if bytes.len() > BYTES_PER_CHUNK {
+        return Err(SSZError::InvalidByteLength {
+            expected: BYTES_PER_CHUNK,
+            got: bytes.len(),
+        });
+    }
+    let mut chunk = [0u8; BYTES_PER_CHUNK];
+    chunk[..bytes.len()].copy_from_slice(bytes);
+    Ok(chunk)
+}
+
 /// Packs bitfield bits into 32-byte chunks, excluding length bit for BitList.
 pub fn pack_bits(bitfield_bytes: &[u8]) -> Vec<[u8; BYTES_PER_CHUNK]> {
     pack(bitfield_bytes)
@@ -57,75 +185,649 @@ pub fn pack_bits(bitfield_bytes: &[u8]) -> Vec<[u8; BYTES_PER_CHUNK]> {
 /// Merkleize a list of 32-byte chunks.
 /// Optionally apply a chunk count limit (e.g., for lists or bitlists).
 pub fn merkleize(chunks: &[[u8; BYTES_PER_CHUNK]], limit: Option<usize>) -> Result<B256, SSZError> {
-    if let Some(limit) = limit {
-        if chunks.len() > limit {
-            return Err(SSZError::ChunkCountExceedsLimit {
-                limit,
-                count: chunks.len(),
-            });
+    merkleize_with::<Sha256Hasher>(chunks, limit)
+}
+
+/// [`merkleize`] with a custom hasher.
+pub fn merkleize_with<H: MerkleHasher>(
+    chunks: &[[u8; BYTES_PER_CHUNK]],
+    limit: Option<usize>,
+) -> Result<B256, SSZError> {
+    if let Some(limit) = limit
+        && chunks.len() > limit
+    {
+        return Err(SSZError::ChunkCountExceedsLimit {
+            limit,
+            count: chunks.len(),
+        });
+    }
+
+    let depth = get_depth(limit.unwrap_or(chunks.len())) as usize;
+    let zero = zero_hashes::<H>(depth);
+    if chunks.is_empty() {
+        return Ok(B256::from(zero[depth]));
+    }
+
+    // Only the populated nodes are hashed; a missing right sibling is the root of an all-zero
+    // subtree of that level, so large limits don't need their padding materialised.
+    let mut layer = chunks.to_vec();
+    for zero in &zero[..depth] {
+        if !layer.len().is_multiple_of(2) {
+            layer.push(*zero);
         }
+        layer = hash_layer::<H>(&layer);
     }
 
-    let padded_len = match limit {
-        Some(l) => next_pow_of_two(l),
-        None => next_pow_of_two(chunks.len()),
-    };
+    Ok(B256::from(layer[0]))
+}
 
-    let mut layer: Vec<[u8; BYTES_PER_CHUNK]> = Vec::with_capacity(padded_len);
-    layer.extend_from_slice(chunks);
+/// [`merkleize`] with its parallel layers hashed on `pool` instead of the global rayon pool.
+///
+/// Lets applications that already manage a pool bound the threads merkleization uses.
+#[cfg(feature = "rayon")]
+pub fn merkleize_in_pool(
+    pool: &rayon::ThreadPool,
+    chunks: &[[u8; BYTES_PER_CHUNK]],
+    limit: Option<usize>,
+) -> Result<B256, SSZError> {
+    pool.install(|| merkleize(chunks, limit))
+}
 
-    // Pad with zero chunks
-    while layer.len() < padded_len {
-        layer.push([0u8; BYTES_PER_CHUNK]);
+/// Returns the roots of all-zero subtrees of depth `0..=depth`, so entry `i` is the root of
+/// `2^i` zero chunks.
+pub(crate) fn zero_hashes<H: MerkleHasher>(depth: usize) -> Vec<[u8; BYTES_PER_CHUNK]> {
+    let mut zero = Vec::with_capacity(depth + 1);
+    zero.push([0u8; BYTES_PER_CHUNK]);
+    for level in 0..depth {
+        zero.push(H::hash_pair(&zero[level], &zero[level]));
     }
+    zero
+}
 
-    if layer.len() == 1 {
-        return Ok(B256::from(layer[0]));
-    }
+/// Layers with at least this many pairs are hashed in parallel under the `rayon` feature;
+/// smaller ones aren't worth the scheduling overhead.
+#[cfg(feature = "rayon")]
+const PARALLEL_MIN_PAIRS: usize = 1 << 10;
 
-    // Merkleize
-    while layer.len() > 1 {
-        let mut next_layer = Vec::with_capacity(layer.len() / 2);
-        for pair in layer.chunks(2) {
-            let left = &pair[0];
-            let right = if pair.len() == 2 { &pair[1] } else { &pair[0] };
+/// Hashes each pair of nodes in `layer` into the layer above it.
+#[cfg(not(feature = "rayon"))]
+fn hash_layer<H: MerkleHasher>(layer: &[[u8; BYTES_PER_CHUNK]]) -> Vec<[u8; BYTES_PER_CHUNK]> {
+    hash_layer_sequential::<H>(layer)
+}
 
-            let mut hasher = Sha256::new();
-            hasher.update(left);
-            hasher.update(right);
-            let hashed = hasher.finalize();
-            next_layer.push(hashed.into());
-        }
-        layer = next_layer;
+/// Hashes each pair of nodes in `layer` into the layer above it, splitting large layers
+/// across the rayon thread pool. The output is identical to the sequential path.
+#[cfg(feature = "rayon")]
+fn hash_layer<H: MerkleHasher>(layer: &[[u8; BYTES_PER_CHUNK]]) -> Vec<[u8; BYTES_PER_CHUNK]> {
+    use rayon::prelude::*;
+
+    if layer.len() / 2 < PARALLEL_MIN_PAIRS {
+        return hash_layer_sequential::<H>(layer);
     }
+    layer.par_chunks(2).map(hash_siblings::<H>).collect()
+}
 
-    Ok(B256::from(layer[0]))
+fn hash_layer_sequential<H: MerkleHasher>(
+    layer: &[[u8; BYTES_PER_CHUNK]],
+) -> Vec<[u8; BYTES_PER_CHUNK]> {
+    layer.chunks(2).map(hash_siblings::<H>).collect()
+}
+
+/// Hashes a pair of sibling nodes; layers are always padded to an even length first.
+fn hash_siblings<H: MerkleHasher>(pair: &[[u8; BYTES_PER_CHUNK]]) -> [u8; BYTES_PER_CHUNK] {
+    H::hash_pair(&pair[0], &pair[1])
+}
+
+/// Merkleizes a container from its field roots, in declaration order.
+///
+/// The roots are padded with zero chunks to the next power of two of the field count.
+pub fn merkleize_fields(field_roots: Vec<B256>) -> Result<B256, SSZError> {
+    let chunks: Vec<[u8; BYTES_PER_CHUNK]> = field_roots.into_iter().map(|root| root.0).collect();
+    merkleize(&chunks, None)
 }
 
 /// Mix in length into a Merkle root (used for lists and bitlists).
+///
+/// The length is encoded as a little-endian `uint64` in the first 8 bytes of a zeroed chunk, so
+/// `len` must fit in a `u64`. SSZ lengths are always bounded by a list limit, so this only
+/// matters on platforms where `usize` is wider than 64 bits.
 pub fn mix_in_length(root: B256, len: usize) -> B256 {
-    let mut hasher = Sha256::new();
-    hasher.update(root.as_slice());
+    mix_in_length_with::<Sha256Hasher>(root, len)
+}
 
+/// [`mix_in_length`] with a custom hasher.
+pub fn mix_in_length_with<H: MerkleHasher>(root: B256, len: usize) -> B256 {
+    debug_assert!(u64::try_from(len).is_ok(), "list length must fit in a u64");
     let mut len_bytes = [0u8; 32];
     len_bytes[..8].copy_from_slice(&(len as u64).to_le_bytes());
-    hasher.update(len_bytes);
-
-    B256::from_slice(&hasher.finalize())
+    mix_in_aux_with::<H>(root, B256::from(len_bytes))
 }
 
 /// Mix in selector (used for unions)
+///
+/// Like [`mix_in_length`], the selector is encoded as a little-endian `uint64` and must fit in a
+/// `u64`.
 pub fn mix_in_selector(root: B256, selector: usize) -> B256 {
-    let mut hasher = Sha256::new();
-    hasher.update(root.as_slice());
+    mix_in_selector_with::<Sha256Hasher>(root, selector)
+}
 
+/// [`mix_in_selector`] with a custom hasher.
+pub fn mix_in_selector_with<H: MerkleHasher>(root: B256, selector: usize) -> B256 {
+    debug_assert!(
+        u64::try_from(selector).is_ok(),
+        "selector must fit in a u64"
+    );
     let mut sel_bytes = [0u8; 32];
     sel_bytes[..8].copy_from_slice(&(selector as u64).to_le_bytes());
-    hasher.update(sel_bytes);
+    mix_in_aux_with::<H>(root, B256::from(sel_bytes))
+}
 
+/// Computes the signing root of an object, i.e. the root of `SigningData { object_root, domain }`.
+///
+/// This is `hash(object_root || domain)`, the message signed by BLS signatures in the consensus spec.
+pub fn compute_signing_root(object_root: B256, domain: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(object_root.as_slice());
+    hasher.update(domain.as_slice());
     B256::from_slice(&hasher.finalize())
 }
 
+/// Merkleizes `items` as an SSZ `List[T, limit]` representing a set.
+///
+/// The items are sorted and duplicates dropped first, so any ordering of the same set produces the
+/// same root. Fails if more than `limit` distinct items remain.
+pub fn merkleize_sorted_unique<T>(items: &[T], limit: usize) -> Result<B256, SSZError>
+where
+    T: Merkleize + SimpleSerialize + SszTypeInfo + Ord + Clone,
+{
+    let mut set = items.to_vec();
+    set.sort();
+    set.dedup();
+    if set.len() > limit {
+        return Err(SSZError::InvalidLength {
+            expected: limit,
+            got: set.len(),
+        });
+    }
+
+    let (chunks, chunk_limit) = if T::is_basic_type() {
+        let elem_size = T::fixed_size().expect("Basic types should have fixed size");
+        let mut serialized = Vec::new();
+        set.serialize(&mut serialized)?;
+        (
+            pack(&serialized),
+            (limit * elem_size).div_ceil(BYTES_PER_CHUNK),
+        )
+    } else {
+        let roots = set
+            .iter()
+            .map(|e| e.hash_tree_root().map(|h| h.0))
+            .collect::<Result<Vec<_>, _>>()?;
+        (roots, limit)
+    };
+
+    let root = merkleize(&chunks, Some(chunk_limit))?;
+    Ok(mix_in_length(root, set.len()))
+}
+
+/// Computes the root of an SSZ `List` from its already-serialized elements, without decoding them.
+///
+/// - For basic element types (`is_basic`), each blob is the element's SSZ encoding and must be
+///   exactly `elem_fixed_size` bytes; the blobs are concatenated and packed into chunks.
+/// - For composite element types, the caller must pass each element's `hash_tree_root` as a
+///   32-byte blob instead, since a serialized composite can't be rooted without its type.
+///
+/// `limit` is the list's element capacity; the element count is mixed in as the length.
+pub fn hash_tree_root_of_serialized(
+    elements: &[Vec<u8>],
+    is_basic: bool,
+    elem_fixed_size: Option<usize>,
+    limit: usize,
+) -> Result<B256, SSZError> {
+    if elements.len() > limit {
+        return Err(SSZError::InvalidLength {
+            expected: limit,
+            got: elements.len(),
+        });
+    }
+
+    let (chunks, chunk_limit) = if is_basic {
+        let elem_size = elem_fixed_size.ok_or(SSZError::InvalidChunkSize)?;
+        let mut serialized = Vec::with_capacity(elements.len() * elem_size);
+        for element in elements {
+            if element.len() != elem_size {
+                return Err(SSZError::InvalidByteLength {
+                    expected: elem_size,
+                    got: element.len(),
+                });
+            }
+            serialized.extend_from_slice(element);
+        }
+        (
+            pack(&serialized),
+            (limit * elem_size).div_ceil(BYTES_PER_CHUNK),
+        )
+    } else {
+        let roots = elements
+            .iter()
+            .map(|root| {
+                <[u8; BYTES_PER_CHUNK]>::try_from(root.as_slice())
+                    .map_err(|_| SSZError::InvalidChunkSize)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        (roots, limit)
+    };
+
+    let root = merkleize(&chunks, Some(chunk_limit))?;
+    Ok(mix_in_length(root, elements.len()))
+}
+
+/// Computes the root of an SSZ list of `total_len` elements from the leading chunks that are
+/// known, treating every later chunk as zero.
+///
+/// `limit` is the list's chunk limit. With all chunks known this is the list root; otherwise it
+/// is the root the list would have if its unknown suffix were zero.
+pub fn partial_list_root(
+    known_leaves: &[[u8; BYTES_PER_CHUNK]],
+    total_len: usize,
+    limit: usize,
+) -> Result<B256, SSZError> {
+    let root = merkleize(known_leaves, Some(limit))?;
+    Ok(mix_in_length(root, total_len))
+}
+
+/// Returns the generalized indices of the proof nodes needed to reconstruct the root from the
+/// leaves at `indices`, sorted in descending order as in the consensus spec `get_helper_indices`.
+pub fn get_helper_indices(indices: &[u64]) -> Vec<u64> {
+    let mut helpers = BTreeSet::new();
+    let mut paths = BTreeSet::new();
+    for &index in indices {
+        let mut node = index;
+        while node > 1 {
+            helpers.insert(node ^ 1);
+            paths.insert(node);
+            node /= 2;
+        }
+    }
+    helpers
+        .iter()
+        .rev()
+        .filter(|index| !paths.contains(index))
+        .copied()
+        .collect()
+}
+
+/// Verifies a Merkle multiproof for the `leaves` at generalized `indices` against `root`.
+///
+/// Follows the consensus spec `calculate_multi_merkle_root`: `proof` must hold the nodes at
+/// [`get_helper_indices`] in that order, and the root is rebuilt bottom-up from leaves and proof.
+pub fn verify_multiproof_indices(
+    root: B256,
+    indices: &[u64],
+    leaves: &[B256],
+    proof: &[B256],
+) -> Result<bool, SSZError> {
+    if leaves.len() != indices.len() {
+        return Err(SSZError::InvalidLength {
+            expected: indices.len(),
+            got: leaves.len(),
+        });
+    }
+    if let Some(&index) = indices.iter().find(|&&index| index == 0) {
+        return Err(SSZError::InvalidGeneralizedIndex { index });
+    }
+
+    let helper_indices = get_helper_indices(indices);
+    if proof.len() != helper_indices.len() {
+        return Err(SSZError::InvalidLength {
+            expected: helper_indices.len(),
+            got: proof.len(),
+        });
+    }
+
+    let mut objects: BTreeMap<u64, B256> = indices
+        .iter()
+        .copied()
+        .zip(leaves.iter().copied())
+        .collect();
+    objects.extend(helper_indices.iter().copied().zip(proof.iter().copied()));
+
+    let mut keys: Vec<u64> = objects.keys().rev().copied().collect();
+    let mut pos = 0;
+    while pos < keys.len() {
+        let key = keys[pos];
+        let parent = key / 2;
+        if key > 1 && objects.contains_key(&(key ^ 1)) && !objects.contains_key(&parent) {
+            let left = objects[&(key & !1)];
+            let right = objects[&(key | 1)];
+            objects.insert(parent, mix_in_aux(left, right));
+            keys.push(parent);
+        }
+        pos += 1;
+    }
+
+    Ok(objects.get(&1) == Some(&root))
+}
+
+/// Single-leaf Merkle proof for the leaf at generalized `index`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub index: u64,
+    pub leaf: B256,
+    /// Sibling nodes from the leaf level up to just below the root.
+    pub branch: Vec<B256>,
+}
+
+impl MerkleProof {
+    /// Returns the root the proof hashes up to.
+    pub fn root(&self) -> Result<B256, SSZError> {
+        check_branch_len(self.index, self.branch.len())?;
+        Ok(self
+            .branch
+            .iter()
+            .enumerate()
+            .fold(self.leaf, |node, (depth, sibling)| {
+                if (self.index >> depth) & 1 == 1 {
+                    mix_in_aux(*sibling, node)
+                } else {
+                    mix_in_aux(node, *sibling)
+                }
+            }))
+    }
+}
+
+/// Merkle multiproof in the layout [`verify_multiproof_indices`] expects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multiproof {
+    pub indices: Vec<u64>,
+    pub leaves: Vec<B256>,
+    /// Nodes at [`get_helper_indices`] of `indices`, in that order.
+    pub proof: Vec<B256>,
+}
+
+impl Multiproof {
+    /// Verifies the multiproof against `root`.
+    pub fn verify(&self, root: B256) -> Result<bool, SSZError> {
+        verify_multiproof_indices(root, &self.indices, &self.leaves, &self.proof)
+    }
+}
+
+/// Checks that a branch for generalized `index` holds one sibling per level below the root.
+fn check_branch_len(index: u64, len: usize) -> Result<(), SSZError> {
+    if index == 0 {
+        return Err(SSZError::InvalidGeneralizedIndex { index });
+    }
+    let depth = index.ilog2() as usize;
+    if len != depth {
+        return Err(SSZError::InvalidLength {
+            expected: depth,
+            got: len,
+        });
+    }
+    Ok(())
+}
+
+/// Merges single-leaf proofs against the same root into one multiproof, dropping the branch
+/// nodes that other proofs already determine.
+///
+/// Fails with [`SSZError::InconsistentProofs`] if the proofs hash to different roots or disagree
+/// on any node they share.
+pub fn combine_proofs(proofs: &[MerkleProof]) -> Result<Multiproof, SSZError> {
+    let mut nodes: BTreeMap<u64, B256> = BTreeMap::new();
+    let mut insert = |index: u64, node: B256| match nodes.insert(index, node) {
+        Some(previous) if previous != node => Err(SSZError::InconsistentProofs { index }),
+        _ => Ok(()),
+    };
+
+    let mut root = None;
+    for proof in proofs {
+        let proof_root = proof.root()?;
+        if root.is_some_and(|root| root != proof_root) {
+            return Err(SSZError::InconsistentProofs { index: 1 });
+        }
+        root = Some(proof_root);
+
+        insert(proof.index, proof.leaf)?;
+        for (depth, sibling) in proof.branch.iter().enumerate() {
+            insert((proof.index >> depth) ^ 1, *sibling)?;
+        }
+    }
+
+    let mut indices: Vec<u64> = proofs.iter().map(|proof| proof.index).collect();
+    indices.sort_unstable();
+    indices.dedup();
+    let leaves = indices.iter().map(|index| nodes[index]).collect();
+    let proof = get_helper_indices(&indices)
+        .iter()
+        .map(|index| nodes[index])
+        .collect();
+
+    Ok(Multiproof {
+        indices,
+        leaves,
+        proof,
+    })
+}
+
+/// Merkle tree over a growing sequence of chunks that keeps every layer, so appending or
+/// updating a chunk only rehashes the path to the root.
+///
+/// The tree is as deep as `merkleize(chunks, limit)`, but each layer only stores the nodes
+/// covering actual chunks; the rest are read from [`zero_hashes`]. Without a limit, crossing a
+/// power of two rebuilds the tree.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedTree {
+    // `layers[level]` holds the populated nodes of that level, from the leaves up to the root
+    layers: Vec<Vec<[u8; BYTES_PER_CHUNK]>>,
+    zero: Vec<[u8; BYTES_PER_CHUNK]>,
+    limit: Option<usize>,
+}
+
+impl CachedTree {
+    /// Builds the tree over `chunks`, padded for at most `limit` chunks.
+    pub(crate) fn new(chunks: &[[u8; BYTES_PER_CHUNK]], limit: Option<usize>) -> Self {
+        let depth = get_depth(limit.unwrap_or(0).max(chunks.len())) as usize;
+        let zero = zero_hashes::<Sha256Hasher>(depth);
+
+        let mut layers = alloc::vec![chunks.to_vec()];
+        for level in 0..depth {
+            let next = layers[level]
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair.get(1).unwrap_or(&zero[level]);
+                    mix_in_aux(B256::from(pair[0]), B256::from(*right)).0
+                })
+                .collect();
+            layers.push(next);
+        }
+
+        Self {
+            layers,
+            zero,
+            limit,
+        }
+    }
+
+    /// Returns the depth of the tree.
+    fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// Returns the node at `index` within `level`, or the zero subtree root past the populated
+    /// width.
+    fn node(&self, level: usize, index: usize) -> [u8; BYTES_PER_CHUNK] {
+        self.layers[level]
+            .get(index)
+            .copied()
+            .unwrap_or(self.zero[level])
+    }
+
+    /// Returns the chunk at `index`, or a zero chunk past the end.
+    pub(crate) fn chunk(&self, index: usize) -> [u8; BYTES_PER_CHUNK] {
+        self.node(0, index)
+    }
+
+    /// Sets the chunk at `index`, which may be at most one past the last chunk.
+    pub(crate) fn set_chunk(&mut self, index: usize, chunk: [u8; BYTES_PER_CHUNK]) {
+        let len = self.layers[0].len();
+        debug_assert!(index <= len, "chunks must be appended in order");
+        if index >= 1 << self.depth() {
+            let mut chunks = self.layers[0].clone();
+            chunks.push(chunk);
+            *self = Self::new(&chunks, self.limit);
+            return;
+        }
+
+        let mut node = chunk;
+        let mut index = index;
+        for level in 0..=self.depth() {
+            if index == self.layers[level].len() {
+                self.layers[level].push(node);
+            } else {
+                self.layers[level][index] = node;
+            }
+            if level < self.depth() {
+                let (left, right) = (self.node(level, index & !1), self.node(level, index | 1));
+                node = mix_in_aux(B256::from(left), B256::from(right)).0;
+                index /= 2;
+            }
+        }
+    }
+
+    /// Returns the root of the tree.
+    pub(crate) fn root(&self) -> B256 {
+        B256::from(self.node(self.depth(), 0))
+    }
+
+    /// Returns the proof for the chunk at `index`, which must be within the padded width.
+    ///
+    /// Siblings past the populated width come from the zero subtree roots, so a proof against a
+    /// large limit costs only its depth.
+    pub(crate) fn prove(&self, index: usize) -> MerkleProof {
+        debug_assert!(index < 1 << self.depth(), "chunk index past the tree width");
+        let branch = (0..self.depth())
+            .map(|level| B256::from(self.node(level, (index >> level) ^ 1)))
+            .collect();
+        MerkleProof {
+            index: ((1 << self.depth()) + index) as u64,
+            leaf: B256::from(self.chunk(index)),
+            branch,
+        }
+    }
+}
+
+/// Merkleizes chunks as they arrive, keeping at most one pending node per tree level.
+#[derive(Debug, Default)]
+struct ChunkStream {
+    // `pending[level]` is a finished left subtree of `2^level` chunks awaiting its sibling
+    pending: Vec<Option<[u8; BYTES_PER_CHUNK]>>,
+    count: usize,
+}
+
+impl ChunkStream {
+    fn push(&mut self, chunk: [u8; BYTES_PER_CHUNK]) {
+        let mut node = chunk;
+        let mut level = 0;
+        loop {
+            if level == self.pending.len() {
+                self.pending.push(None);
+            }
+            match self.pending[level].take() {
+                Some(left) => {
+                    node = mix_in_aux(B256::from(left), B256::from(node)).0;
+                    level += 1;
+                }
+                None => {
+                    self.pending[level] = Some(node);
+                    break;
+                }
+            }
+        }
+        self.count += 1;
+    }
+
+    /// Returns the root of the pushed chunks zero-padded to `next_pow_of_two(limit)` leaves.
+    fn finish(self, limit: usize) -> Result<B256, SSZError> {
+        if self.count > limit {
+            return Err(SSZError::ChunkCountExceedsLimit {
+                limit,
+                count: self.count,
+            });
+        }
+
+        let depth = get_depth(limit) as usize;
+        let mut zero = [0u8; BYTES_PER_CHUNK];
+        let mut node: Option<[u8; BYTES_PER_CHUNK]> = None;
+        for level in 0..depth {
+            let pending = self.pending.get(level).copied().flatten();
+            node = match (pending, node) {
+                (Some(left), Some(right)) => Some(mix_in_aux(left.into(), right.into()).0),
+                (Some(left), None) => Some(mix_in_aux(left.into(), zero.into()).0),
+                (None, Some(left)) => Some(mix_in_aux(left.into(), zero.into()).0),
+                (None, None) => None,
+            };
+            zero = mix_in_aux(zero.into(), zero.into()).0;
+        }
+
+        let full = self.pending.get(depth).copied().flatten();
+        Ok(B256::from(node.or(full).unwrap_or(zero)))
+    }
+}
+
+/// Checks that the elements yielded by `iter` have the root of an SSZ `List[T, limit]` equal to
+/// `expected`, without collecting them.
+///
+/// Elements are packed and hashed as they arrive, so memory use is logarithmic in the list length.
+/// Only basic element types are supported; fails if more than `limit` elements are yielded.
+pub fn verify_list_root<T, I>(iter: I, limit: usize, expected: B256) -> Result<bool, SSZError>
+where
+    T: SimpleSerialize + SszTypeInfo,
+    I: IntoIterator<Item = T>,
+{
+    if !T::is_basic_type() {
+        return Err(SSZError::TypeMismatch {
+            context: alloc::format!(
+                "verify_list_root needs a basic element type, got {}",
+                core::any::type_name::<T>()
+            ),
+        });
+    }
+    let elem_size = T::fixed_size().expect("Basic types should have fixed size");
+
+    let mut stream = ChunkStream::default();
+    let mut chunk = [0u8; BYTES_PER_CHUNK];
+    let mut filled = 0;
+    let mut len = 0;
+    let mut scratch = Vec::with_capacity(elem_size);
+    for element in iter {
+        len += 1;
+        if len > limit {
+            return Err(SSZError::InvalidLength {
+                expected: limit,
+                got: len,
+            });
+        }
+
+        scratch.clear();
+        element.serialize(&mut scratch)?;
+        for &byte in &scratch {
+            chunk[filled] = byte;
+            filled += 1;
+            if filled == BYTES_PER_CHUNK {
+                stream.push(chunk);
+                chunk = [0u8; BYTES_PER_CHUNK];
+                filled = 0;
+            }
+        }
+    }
+    if filled > 0 {
+        stream.push(chunk);
+    }
+
+    let root = stream.finish((limit * elem_size).div_ceil(BYTES_PER_CHUNK))?;
+    Ok(mix_in_length(root, len) == expected)
+}
+
 /// Helper enum to represent type metadata for chunk_count
 pub enum SSZType {
     Basic { size: usize },
@@ -138,21 +840,648 @@ pub enum SSZType {
     Container { field_count: usize },
 }
 
+/// Merkleizes `chunks` over the progressive tree of EIP-7916 (`merkleize_progressive`).
+///
+/// The first `base_size` chunks form a binary subtree padded to `base_size` leaves, which is the
+/// right child of the root; the remaining chunks recurse through the left child with subtrees
+/// `scale` times larger. No chunks at all give the zero chunk:
+///
+/// ```text
+/// merkleize_progressive(chunks, n) = hash(merkleize_progressive(chunks[n..], n * scale),
+///                                         merkleize(chunks[..n], n))
+/// ```
+///
+/// The EIP uses `base_size = 1` and `scale = 4`. A zero `base_size` or a `scale` below 2 is
+/// rejected, so subtrees grow geometrically and the recursion is at most about
+/// `log2(chunks.len())` levels deep.
 pub fn merkleize_progressive_list(
     chunks: &[[u8; 32]],
     base_size: usize,
     scale: usize,
 ) -> Result<B256, SSZError> {
-    if chunks.len() <= base_size {
-        let mut padded = chunks.to_vec();
-        while padded.len() < base_size {
-            padded.push([0u8; 32]);
+    check_progressive_shape(base_size, scale)?;
+    if chunks.is_empty() {
+        return Ok(B256::ZERO);
+    }
+
+    let (subtree, rest) = chunks.split_at(base_size.min(chunks.len()));
+    let subtree_root = merkleize(subtree, Some(base_size))?;
+    let rest_root = merkleize_progressive_list(rest, base_size.saturating_mul(scale), scale)?;
+    Ok(mix_in_aux(rest_root, subtree_root))
+}
+
+/// Rejects progressive tree shapes whose levels don't grow: a zero base size or a scale below 2.
+fn check_progressive_shape(base_size: usize, scale: usize) -> Result<(), SSZError> {
+    if base_size == 0 || scale < 2 {
+        return Err(SSZError::InvalidProgressiveShape { base_size, scale });
+    }
+    Ok(())
+}
+
+/// Number of chunks held by `level` of a progressive list.
+///
+/// A progressive tree hangs a complete subtree off each level as the right child, and recurses
+/// into the remaining levels through the left child: level 0 holds `base_size` chunks and each
+/// later level `scale` times as many as the one before, so with `base_size = 1, scale = 4` the
+/// levels hold 1, 4, 16, 64, ... chunks. The subtree at `level` sits `level + 1` hashes below
+/// the root, plus its own depth.
+///
+/// Fails if `base_size` is zero, `scale` is below 2, or the capacity overflows `usize`.
+pub fn progressive_level_capacity(
+    level: usize,
+    base_size: usize,
+    scale: usize,
+) -> Result<usize, SSZError> {
+    check_progressive_shape(base_size, scale)?;
+    u32::try_from(level)
+        .ok()
+        .and_then(|exp| scale.checked_pow(exp))
+        .and_then(|factor| factor.checked_mul(base_size))
+        .ok_or(SSZError::ProgressiveLevelOverflow { level })
+}
+
+/// Returns the level of a progressive list that holds chunk `index`.
+///
+/// See [`progressive_level_capacity`] for the geometry. Fails if `base_size` is zero or `scale`
+/// is below 2.
+pub fn progressive_level_for_index(
+    index: usize,
+    base_size: usize,
+    scale: usize,
+) -> Result<usize, SSZError> {
+    check_progressive_shape(base_size, scale)?;
+    let mut level = 0;
+    let mut level_start = 0usize;
+    let mut capacity = base_size;
+    // Capacities at least double, so every index is placed within `usize::BITS` levels. A level
+    // whose end overflows `usize` holds every remaining index.
+    while level < usize::BITS as usize {
+        match level_start.checked_add(capacity) {
+            Some(level_end) if index >= level_end => {
+                level_start = level_end;
+                capacity = capacity.saturating_mul(scale);
+                level += 1;
+            }
+            _ => break,
+        }
+    }
+    Ok(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_mix_in_length_large_value() {
+        let len = 0x0102_0304_0506_0708usize;
+        let root = mix_in_length(B256::ZERO, len);
+
+        let mut len_chunk = [0u8; BYTES_PER_CHUNK];
+        len_chunk[..8].copy_from_slice(&[8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(root, mix_in_aux(B256::ZERO, B256::from(len_chunk)));
+        assert_eq!(
+            root,
+            B256::from(hex!(
+                "04dc8f6d159e2ce4bfc12ba63c55541e15c4b156b74e5d7444de905b9cfeec18"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_compute_signing_root() {
+        let root = compute_signing_root(B256::ZERO, B256::ZERO);
+        assert_eq!(
+            root,
+            B256::from(hex!(
+                "f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"
+            ))
+        );
+
+        let object_root = B256::repeat_byte(1);
+        let domain = B256::repeat_byte(2);
+        let root = compute_signing_root(object_root, domain);
+        assert_eq!(
+            root,
+            B256::from(hex!(
+                "f818afd37a6dc3bc92fb44731011277006db4efa6e9023cd7468c02335d22a4d"
+            ))
+        );
+        assert_eq!(
+            root,
+            merkleize(&[object_root.0, domain.0], None).expect("can merkleize")
+        );
+    }
+
+    #[test]
+    fn test_verify_multiproof_indices() {
+        // Generalized indices 8..16 hold the leaves of an 8-leaf tree; 1 is the root.
+        let mut tree = [B256::ZERO; 16];
+        for (i, node) in tree[8..].iter_mut().enumerate() {
+            *node = B256::repeat_byte(i as u8 + 1);
         }
-        return Ok(mix_in_aux(merkleize(&padded, None)?, B256::ZERO));
+        for i in (1..8).rev() {
+            tree[i] = mix_in_aux(tree[2 * i], tree[2 * i + 1]);
+        }
+        let leaves: Vec<_> = tree[8..].iter().map(|leaf| leaf.0).collect();
+        let root = merkleize(&leaves, None).expect("can merkleize");
+        assert_eq!(tree[1], root);
+
+        let indices = [9, 14];
+        let helper_indices = get_helper_indices(&indices);
+        assert_eq!(helper_indices, alloc::vec![15, 8, 6, 5]);
+
+        let leaves = [tree[9], tree[14]];
+        let proof: Vec<_> = helper_indices.iter().map(|&i| tree[i as usize]).collect();
+        assert_eq!(
+            verify_multiproof_indices(root, &indices, &leaves, &proof),
+            Ok(true)
+        );
+
+        let wrong_leaves = [tree[9], tree[13]];
+        assert_eq!(
+            verify_multiproof_indices(root, &indices, &wrong_leaves, &proof),
+            Ok(false)
+        );
+        assert_eq!(
+            verify_multiproof_indices(root, &indices, &leaves, &proof[1..]),
+            Err(SSZError::InvalidLength {
+                expected: 4,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_merkleize_sorted_unique() {
+        let sorted = merkleize_sorted_unique(&[1u64, 2, 3, 5], 8).expect("can merkleize");
+        let unsorted = merkleize_sorted_unique(&[5u64, 2, 3, 1, 2, 5], 8).expect("can merkleize");
+        assert_eq!(sorted, unsorted);
+
+        let mut packed = [0u8; BYTES_PER_CHUNK];
+        for (i, v) in [1u64, 2, 3, 5].iter().enumerate() {
+            packed[i * 8..(i + 1) * 8].copy_from_slice(&v.to_le_bytes());
+        }
+        let expected = mix_in_length(merkleize(&[packed], Some(2)).expect("can merkleize"), 4);
+        assert_eq!(sorted, expected);
+
+        assert_eq!(
+            merkleize_sorted_unique(&[3u64, 1, 2], 2),
+            Err(SSZError::InvalidLength {
+                expected: 2,
+                got: 3
+            })
+        );
     }
 
-    let (subtree, rest) = chunks.split_at(base_size);
-    let subtree_root = merkleize(subtree, None)?;
-    let rest_root = merkleize_progressive_list(rest, base_size * scale, scale)?;
-    Ok(mix_in_aux(subtree_root, rest_root))
+    #[test]
+    fn test_cached_tree_matches_merkleize() {
+        let chunks: Vec<_> = (1..=9u8).map(|i| [i; BYTES_PER_CHUNK]).collect();
+        let mut tree = CachedTree::new(&[], None);
+        assert_eq!(tree.root(), merkleize(&[], None).unwrap());
+        for (i, chunk) in chunks.iter().enumerate() {
+            tree.set_chunk(i, *chunk);
+            assert_eq!(tree.root(), merkleize(&chunks[..=i], None).unwrap());
+        }
+
+        tree.set_chunk(3, [0xaa; BYTES_PER_CHUNK]);
+        let mut updated = chunks.clone();
+        updated[3] = [0xaa; BYTES_PER_CHUNK];
+        assert_eq!(tree.root(), merkleize(&updated, None).unwrap());
+        assert_eq!(tree.chunk(3), [0xaa; BYTES_PER_CHUNK]);
+        assert_eq!(tree.chunk(9), [0u8; BYTES_PER_CHUNK]);
+
+        let mut tree = CachedTree::new(&[], Some(16));
+        for (i, chunk) in chunks.iter().enumerate() {
+            tree.set_chunk(i, *chunk);
+            assert_eq!(tree.root(), merkleize(&chunks[..=i], Some(16)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_merkleize_large_limit() {
+        let chunks: Vec<_> = (1..=3u8).map(|i| [i; BYTES_PER_CHUNK]).collect();
+
+        // Three chunks at depth 2, then hashed with the zero subtree of each level up to 40.
+        let mut expected = merkleize(&chunks, Some(4)).unwrap();
+        let mut zero = merkleize(&[], Some(4)).unwrap();
+        for _ in 2..40 {
+            expected = mix_in_aux(expected, zero);
+            zero = mix_in_aux(zero, zero);
+        }
+        assert_eq!(merkleize(&chunks, Some(1 << 40)).unwrap(), expected);
+        assert_eq!(merkleize(&[], Some(1 << 40)).unwrap(), zero);
+
+        let mut tree = CachedTree::new(&chunks[..1], Some(1 << 40));
+        tree.set_chunk(1, chunks[1]);
+        tree.set_chunk(2, chunks[2]);
+        assert_eq!(tree.root(), expected);
+        assert_eq!(CachedTree::new(&[], Some(1 << 40)).root(), zero);
+    }
+
+    #[test]
+    fn test_hash_tree_root_of_serialized() {
+        use crate::ssz_list::List;
+
+        let values = alloc::vec![1u64, 2, 3, 4];
+        let blobs: Vec<Vec<u8>> = values.iter().map(|v| v.to_le_bytes().to_vec()).collect();
+        let root = hash_tree_root_of_serialized(&blobs, true, Some(8), 4).expect("can merkleize");
+        let expected = List::<u64, 4>::new(values.clone())
+            .unwrap()
+            .hash_tree_root()
+            .unwrap();
+        assert_eq!(root, expected);
+
+        let mut serialized = Vec::new();
+        values.serialize(&mut serialized).unwrap();
+        let padded = merkleize(&pack(&serialized), Some(4)).unwrap();
+        assert_eq!(
+            hash_tree_root_of_serialized(&blobs, true, Some(8), 16).unwrap(),
+            mix_in_length(padded, 4)
+        );
+
+        let roots: Vec<Vec<u8>> = values
+            .iter()
+            .map(|v| alloc::vec![*v as u8; BYTES_PER_CHUNK])
+            .collect();
+        let chunks: Vec<_> = roots
+            .iter()
+            .map(|r| <[u8; BYTES_PER_CHUNK]>::try_from(r.as_slice()).unwrap())
+            .collect();
+        assert_eq!(
+            hash_tree_root_of_serialized(&roots, false, None, 4).unwrap(),
+            mix_in_length(merkleize(&chunks, Some(4)).unwrap(), 4)
+        );
+
+        assert_eq!(
+            hash_tree_root_of_serialized(&[alloc::vec![1u8; 7]], true, Some(8), 4),
+            Err(SSZError::InvalidByteLength {
+                expected: 8,
+                got: 7
+            })
+        );
+        assert_eq!(
+            hash_tree_root_of_serialized(&blobs, false, None, 4),
+            Err(SSZError::InvalidChunkSize)
+        );
+    }
+
+    #[test]
+    fn test_merkleize_fields() {
+        let a_root = 12u32.hash_tree_root().unwrap();
+        let b_root = 6u8.hash_tree_root().unwrap();
+        // Root of `container::Foo { a: 12, b: 6 }`.
+        assert_eq!(
+            merkleize_fields(alloc::vec![a_root, b_root]).unwrap(),
+            B256::from(hex!(
+                "e922cefc3d48d862e694c6c4615f407767d46ca09b4d476302f852fe9b5e8ce1"
+            ))
+        );
+
+        let roots = alloc::vec![a_root, b_root, a_root];
+        assert_eq!(
+            merkleize_fields(roots).unwrap(),
+            merkleize(&[a_root.0, b_root.0, a_root.0, [0u8; 32]], None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_chunk_stream_matches_merkleize() {
+        for count in 0..=9usize {
+            let chunks: Vec<[u8; BYTES_PER_CHUNK]> =
+                (0..count).map(|i| [i as u8 + 1; BYTES_PER_CHUNK]).collect();
+            for limit in [count, count + 1, 16] {
+                let mut stream = ChunkStream::default();
+                chunks.iter().for_each(|&chunk| stream.push(chunk));
+                assert_eq!(
+                    stream.finish(limit).unwrap(),
+                    merkleize(&chunks, Some(limit)).unwrap(),
+                    "count {count}, limit {limit}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_list_root() {
+        use crate::ssz_list::List;
+
+        let values: Vec<u64> = (0..1000).map(|i| i * 7).collect();
+        let root = List::<u64, 2048>::new(values.clone())
+            .unwrap()
+            .hash_tree_root()
+            .unwrap();
+        assert_eq!(
+            root,
+            B256::from(hex!(
+                "39871184caeb5ffc2168e1a25f29966eafab2a6abf89e3f515954ac5c15dc0e1"
+            ))
+        );
+
+        assert_eq!(
+            verify_list_root(values.iter().copied(), 2048, root),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_list_root(values.iter().copied().skip(1), 2048, root),
+            Ok(false)
+        );
+        assert_eq!(
+            verify_list_root(values.iter().copied(), 4096, root),
+            Ok(false)
+        );
+        assert!(verify_list_root(values.iter().copied(), 999, root).is_err());
+        assert!(verify_list_root([Vec::<u8>::new()], 4, root).is_err());
+    }
+
+    #[test]
+    fn test_partial_list_root() {
+        let leaves: Vec<[u8; BYTES_PER_CHUNK]> = (1..=5).map(|i| [i; BYTES_PER_CHUNK]).collect();
+        assert_eq!(
+            partial_list_root(&leaves, 5, 8).unwrap(),
+            mix_in_length(merkleize(&leaves, Some(8)).unwrap(), 5)
+        );
+
+        // Known prefix of a list whose remaining chunks are zero.
+        let mut full = leaves[..3].to_vec();
+        full.extend([[0u8; BYTES_PER_CHUNK]; 2]);
+        assert_eq!(
+            partial_list_root(&leaves[..3], 5, 8).unwrap(),
+            mix_in_length(merkleize(&full, Some(8)).unwrap(), 5)
+        );
+
+        assert!(partial_list_root(&leaves, 5, 4).is_err());
+    }
+
+    #[test]
+    fn test_combine_proofs() {
+        let leaves: Vec<[u8; BYTES_PER_CHUNK]> = (0..8).map(|i| [i; BYTES_PER_CHUNK]).collect();
+        let tree = CachedTree::new(&leaves, Some(8));
+        let prove = |position: usize| MerkleProof {
+            index: 8 + position as u64,
+            leaf: B256::from(leaves[position]),
+            branch: (0..3)
+                .map(|depth| B256::from(tree.layers[depth][(position >> depth) ^ 1]))
+                .collect(),
+        };
+
+        let proofs = [prove(1), prove(6)];
+        for proof in &proofs {
+            assert_eq!(proof.root().unwrap(), tree.root());
+        }
+
+        let multiproof = combine_proofs(&proofs).expect("proofs share a root");
+        assert_eq!(multiproof.indices, alloc::vec![9, 14]);
+        // Six branch nodes collapse to the four that neither path determines.
+        assert_eq!(multiproof.proof.len(), 4);
+        assert_eq!(multiproof.verify(tree.root()), Ok(true));
+        assert_eq!(multiproof.verify(B256::ZERO), Ok(false));
+
+        let mut forged = prove(6);
+        forged.leaf = B256::repeat_byte(0xff);
+        assert_eq!(
+            combine_proofs(&[prove(1), forged]),
+            Err(SSZError::InconsistentProofs { index: 1 })
+        );
+
+        let mut short = prove(1);
+        short.branch.pop();
+        assert!(combine_proofs(&[short]).is_err());
+    }
+
+    #[test]
+    fn test_pad_to_chunk() {
+        let mut expected = [0u8; BYTES_PER_CHUNK];
+        expected[..3].copy_from_slice(&[0xaa, 0xbb, 0xcc]);
+        assert_eq!(pad_to_chunk(&[0xaa, 0xbb, 0xcc]), Ok(expected));
+        assert_eq!(pad_to_chunk(&[]), Ok([0u8; BYTES_PER_CHUNK]));
+        assert_eq!(pad_to_chunk(&[7; 32]), Ok([7u8; BYTES_PER_CHUNK]));
+        assert_eq!(
+            pad_to_chunk(&[0; 33]),
+            Err(SSZError::InvalidByteLength {
+                expected: 32,
+                got: 33
+            })
+        );
+    }
+
+    #[test]
+    fn test_merkleize_progressive_list() {
+        let chunks: Vec<[u8; BYTES_PER_CHUNK]> = (1..=22u8).map(|i| [i; BYTES_PER_CHUNK]).collect();
+        let root = |n: usize| merkleize_progressive_list(&chunks[..n], 1, 4).unwrap();
+
+        assert_eq!(root(0), B256::ZERO);
+        // One chunk is the right child of the root, beside an empty left subtree.
+        assert_eq!(root(1), mix_in_aux(B256::ZERO, B256::from(chunks[0])));
+        // Two chunks: the second opens the 4-leaf subtree on the left.
+        let second = mix_in_aux(B256::ZERO, merkleize(&chunks[1..2], Some(4)).unwrap());
+        assert_eq!(root(2), mix_in_aux(second, B256::from(chunks[0])));
+
+        // Reference roots from the EIP-7916 `merkleize_progressive` pseudocode, crossing into
+        // the third (16-leaf) and fourth (64-leaf) subtrees.
+        let expected = [
+            (
+                2,
+                hex!("e5380d39a5495072e94a3fba60868c88da868b188a7cd4cc9542fe7277a7a741"),
+            ),
+            (
+                5,
+                hex!("74b91a7db5cfd89a1626f18be64f89c5fc3d29590c1ca3fcf268d076b0a84e5c"),
+            ),
+            (
+                6,
+                hex!("c32bd031d91fcd1f3f86297cf2395d1404a9d4560cd272b20b27fe3257feaadc"),
+            ),
+            (
+                22,
+                hex!("2fdf45027b7710286eefdd7ae83b7cd9853452d3820a7fb33a48361a6911d2c8"),
+            ),
+        ];
+        for (n, root_hex) in expected {
+            assert_eq!(root(n), B256::from(root_hex), "{n} chunks");
+        }
+    }
+
+    #[test]
+    fn test_progressive_level_geometry() {
+        let capacities: Vec<usize> = (0..4)
+            .map(|l| progressive_level_capacity(l, 1, 4).unwrap())
+            .collect();
+        assert_eq!(capacities, [1, 4, 16, 64]);
+
+        // Levels cover chunks [0], [1, 5), [5, 21), [21, 85).
+        let levels: Vec<usize> = [0, 1, 4, 5, 20, 21, 84, 85]
+            .iter()
+            .map(|&index| progressive_level_for_index(index, 1, 4).unwrap())
+            .collect();
+        assert_eq!(levels, [0, 1, 1, 2, 2, 3, 3, 4]);
+
+        assert_eq!(
+            progressive_level_capacity(64, 1, 4),
+            Err(SSZError::ProgressiveLevelOverflow { level: 64 })
+        );
+        assert!(progressive_level_for_index(usize::MAX, 1, 4).is_ok());
+        assert!(progressive_level_for_index(usize::MAX, usize::MAX, usize::MAX).is_ok());
+        // With the smallest growing shape, levels end at 2^(level + 1) - 1.
+        assert_eq!(
+            progressive_level_for_index(usize::MAX, 1, 2),
+            Ok(usize::BITS as usize)
+        );
+
+        let zero_base = || SSZError::InvalidProgressiveShape {
+            base_size: 0,
+            scale: 4,
+        };
+        assert_eq!(progressive_level_capacity(0, 0, 4), Err(zero_base()));
+        assert_eq!(progressive_level_for_index(3, 0, 4), Err(zero_base()));
+        assert_eq!(
+            merkleize_progressive_list(&[[1u8; 32]], 0, 4),
+            Err(zero_base())
+        );
+        assert!(progressive_level_for_index(3, 1, 0).is_err());
+        assert!(merkleize_progressive_list(&[[1u8; 32]; 3], 1, 0).is_err());
+
+        // A scale of 1 never grows the levels, so it is rejected rather than walked one chunk
+        // at a time.
+        let unit_scale = || SSZError::InvalidProgressiveShape {
+            base_size: 1,
+            scale: 1,
+        };
+        assert_eq!(progressive_level_capacity(3, 1, 1), Err(unit_scale()));
+        assert_eq!(
+            progressive_level_for_index(usize::MAX, 1, 1),
+            Err(unit_scale())
+        );
+        assert_eq!(
+            merkleize_progressive_list(&[[1u8; 32]; 3], 1, 1),
+            Err(unit_scale())
+        );
+    }
+
+    #[test]
+    fn test_get_generalized_index() {
+        use crate::ssz_list::List;
+
+        // Container with 3 fields: 4 padded leaves.
+        let indices: Vec<u64> = (0..3).map(|i| get_generalized_index(3, i)).collect();
+        assert_eq!(indices, [4, 5, 6]);
+
+        // `List[uint64, 8]`: 2 chunks under the length mix-in, whose length node is index 3.
+        let list = List::<u64, 8>::new((0..8).collect()).unwrap();
+        for i in 0..8 {
+            let index = get_generalized_index_mixed_in(List::<u64, 8>::chunk_count(), i / 4);
+            assert_eq!(index, 4 + i as u64 / 4);
+            assert_eq!(list.prove(i).unwrap().index, index);
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "outside a tree of 4 leaves")]
+    fn test_get_generalized_index_rejects_position_past_padding() {
+        get_generalized_index(3, 4);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_merkleize_matches_sequential() {
+        let chunks: Vec<[u8; BYTES_PER_CHUNK]> = (0..(1u32 << 14) + 3)
+            .map(|i| {
+                let mut chunk = [0u8; BYTES_PER_CHUNK];
+                chunk[..4].copy_from_slice(&i.to_le_bytes());
+                chunk
+            })
+            .collect();
+
+        let mut layer = chunks.clone();
+        layer.resize(next_pow_of_two(chunks.len()), [0u8; BYTES_PER_CHUNK]);
+        while layer.len() > 1 {
+            layer = hash_layer_sequential::<Sha256Hasher>(&layer);
+        }
+
+        assert_eq!(merkleize(&chunks, None), Ok(B256::from(layer[0])));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_merkleize_in_pool_matches_global_pool() {
+        let chunks: Vec<[u8; BYTES_PER_CHUNK]> = (0..(1u32 << 12) + 5)
+            .map(|i| {
+                let mut chunk = [0u8; BYTES_PER_CHUNK];
+                chunk[..4].copy_from_slice(&i.to_le_bytes());
+                chunk
+            })
+            .collect();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+
+        let mut layer = chunks.clone();
+        layer.resize(1 << 13, [0u8; BYTES_PER_CHUNK]);
+        while layer.len() > 1 {
+            layer = hash_layer_sequential::<Sha256Hasher>(&layer);
+        }
+        let root = merkleize_in_pool(&pool, &chunks, Some(1 << 13));
+        assert_eq!(root, Ok(B256::from(layer[0])));
+        assert_eq!(root, merkleize(&chunks, Some(1 << 13)));
+        assert_eq!(
+            merkleize_in_pool(&pool, &chunks, Some(1 << 11)),
+            Err(SSZError::ChunkCountExceedsLimit {
+                limit: 1 << 11,
+                count: chunks.len()
+            })
+        );
+    }
+
+    /// Combines nodes by XOR, so roots can be worked out by hand.
+    struct XorHasher;
+
+    impl MerkleHasher for XorHasher {
+        fn hash_pair(
+            left: &[u8; BYTES_PER_CHUNK],
+            right: &[u8; BYTES_PER_CHUNK],
+        ) -> [u8; BYTES_PER_CHUNK] {
+            core::array::from_fn(|i| left[i] ^ right[i])
+        }
+    }
+
+    #[test]
+    fn test_merkleize_with_custom_hasher() {
+        let chunks = [
+            [1u8; BYTES_PER_CHUNK],
+            [2u8; BYTES_PER_CHUNK],
+            [4u8; BYTES_PER_CHUNK],
+        ];
+
+        // XOR of every leaf, the zero padding chunk included.
+        let root = merkleize_with::<XorHasher>(&chunks, None).unwrap();
+        assert_eq!(root, B256::from([7u8; BYTES_PER_CHUNK]));
+        assert_eq!(
+            merkleize_with::<XorHasher>(&chunks, Some(16)).unwrap(),
+            root
+        );
+
+        let mut expected = [7u8; BYTES_PER_CHUNK];
+        expected[0] ^= 3;
+        assert_eq!(
+            mix_in_length_with::<XorHasher>(root, 3),
+            B256::from(expected)
+        );
+        assert_eq!(
+            mix_in_selector_with::<XorHasher>(root, 3),
+            B256::from(expected)
+        );
+
+        // The default hasher is unchanged.
+        assert_eq!(
+            merkleize_with::<Sha256Hasher>(&chunks, None),
+            merkleize(&chunks, None)
+        );
+        assert_eq!(
+            mix_in_length_with::<Sha256Hasher>(root, 3),
+            mix_in_length(root, 3)
+        );
+    }
 }
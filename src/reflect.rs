@@ -0,0 +1,114 @@
+//! Dynamic bridge between SSZ types and `serde_json::Value`.
+
+use crate::SSZError;
+use alloc::{format, string::ToString};
+use alloy_primitives::U256;
+use serde_json::Value;
+
+/// The `SszReflect` trait converts a type to and from a dynamic `serde_json::Value` tree.
+pub trait SszReflect: Sized {
+    /// Names of the fields in declaration order. Empty for non-container types.
+    fn field_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Converts the value into a `serde_json::Value`.
+    fn to_obj(&self) -> Value;
+
+    /// Parses the value back from a `serde_json::Value`.
+    fn from_obj(value: &Value) -> Result<Self, SSZError>;
+}
+
+impl SszReflect for bool {
+    fn to_obj(&self) -> Value {
+        Value::Bool(*self)
+    }
+
+    fn from_obj(value: &Value) -> Result<Self, SSZError> {
+        value
+            .as_bool()
+            .ok_or_else(|| SSZError::DeserializationError(format!("expected bool, got {value}")))
+    }
+}
+
+macro_rules! impl_uint_reflect {
+    ($type:ty) => {
+        impl SszReflect for $type {
+            fn to_obj(&self) -> Value {
+                Value::from(*self)
+            }
+
+            fn from_obj(value: &Value) -> Result<Self, SSZError> {
+                value
+                    .as_u64()
+                    .and_then(|v| <$type>::try_from(v).ok())
+                    .ok_or_else(|| {
+                        SSZError::DeserializationError(format!(
+                            "expected {}, got {value}",
+                            stringify!($type)
+                        ))
+                    })
+            }
+        }
+    };
+}
+
+impl_uint_reflect!(u8);
+impl_uint_reflect!(u16);
+impl_uint_reflect!(u32);
+impl_uint_reflect!(u64);
+
+/// `u128` does not fit in a JSON number, so it is encoded as a decimal string.
+impl SszReflect for u128 {
+    fn to_obj(&self) -> Value {
+        Value::String(self.to_string())
+    }
+
+    fn from_obj(value: &Value) -> Result<Self, SSZError> {
+        value
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| SSZError::DeserializationError(format!("expected u128, got {value}")))
+    }
+}
+
+/// `U256` does not fit in a JSON number, so it is encoded as a decimal string.
+impl SszReflect for U256 {
+    fn to_obj(&self) -> Value {
+        Value::String(self.to_string())
+    }
+
+    fn from_obj(value: &Value) -> Result<Self, SSZError> {
+        value
+            .as_str()
+            .and_then(|s| U256::from_str_radix(s, 10).ok())
+            .ok_or_else(|| SSZError::DeserializationError(format!("expected U256, got {value}")))
+    }
+}
+
+/// Reads a named field from a JSON object describing a container.
+pub fn reflect_field<T: SszReflect>(value: &Value, name: &str) -> Result<T, SSZError> {
+    let field = value
+        .get(name)
+        .ok_or_else(|| SSZError::DeserializationError(format!("missing field `{name}`")))?;
+    T::from_obj(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_basic_reflect_roundtrip() {
+        assert_eq!(true.to_obj(), json!(true));
+        assert_eq!(42u16.to_obj(), json!(42));
+        assert_eq!(u16::from_obj(&json!(42)), Ok(42));
+        assert!(u8::from_obj(&json!(256)).is_err());
+        assert!(u32::from_obj(&json!("1")).is_err());
+
+        let big = U256::MAX;
+        assert_eq!(U256::from_obj(&big.to_obj()), Ok(big));
+        assert_eq!(u128::from_obj(&u128::MAX.to_obj()), Ok(u128::MAX));
+    }
+}
@@ -0,0 +1,351 @@
+//! Stable digests of SSZ type schemas.
+
+use crate::{BitList, BitVector, fixed_vectors::FixedVector, ssz_list::List};
+use alloc::{format, string::String, vec::Vec};
+use alloy_primitives::{B256, Bytes, FixedBytes, U256};
+use sha2::{Digest, Sha256};
+
+/// The `SszSchema` trait describes a type's SSZ schema independently of any value.
+pub trait SszSchema {
+    /// Canonical description of the schema, e.g. `container(uint64,list(uint8,256))`.
+    fn ssz_schema() -> String;
+
+    /// SHA-256 digest of [`SszSchema::ssz_schema`].
+    fn ssz_type_hash() -> B256 {
+        B256::from_slice(&Sha256::digest(Self::ssz_schema().as_bytes()))
+    }
+
+    /// Machine-readable form of [`SszSchema::ssz_schema`], with each type's kind, capacities
+    /// and fixed size (`null` when variable-size).
+    ///
+    /// There is no default, since it can't be recovered from the description without guessing
+    /// at structure such as field names. Containers implement it with
+    /// [`container_schema_json`].
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value;
+}
+
+/// Builds the canonical description of a container from its field types, in order.
+pub fn container_schema(fields: &[String]) -> String {
+    format!("container({})", fields.join(","))
+}
+
+/// Builds the machine-readable description of a container from its named field schemas, in
+/// order.
+#[cfg(feature = "reflect")]
+pub fn container_schema_json(fields: &[(&str, serde_json::Value)]) -> serde_json::Value {
+    let fields: Vec<serde_json::Value> = fields
+        .iter()
+        .map(|(name, schema)| serde_json::json!({ "name": name, "type": schema }))
+        .collect();
+    schema_json::container(fields)
+}
+
+#[cfg(feature = "reflect")]
+mod schema_json {
+    use alloc::vec::Vec;
+    use serde_json::{Value, json};
+
+    pub(super) fn uint(bits: usize) -> Value {
+        json!({ "kind": "uint", "bits": bits, "fixed_size": bits / 8 })
+    }
+
+    pub(super) fn boolean() -> Value {
+        json!({ "kind": "boolean", "fixed_size": 1 })
+    }
+
+    pub(super) fn vector(element: Value, length: usize) -> Value {
+        let fixed_size = element["fixed_size"]
+            .as_u64()
+            .map(|size| size * length as u64);
+        json!({ "kind": "vector", "element": element, "length": length, "fixed_size": fixed_size })
+    }
+
+    pub(super) fn list(element: Value, limit: usize) -> Value {
+        json!({ "kind": "list", "element": element, "limit": limit, "fixed_size": null })
+    }
+
+    pub(super) fn unbounded_vector(element: Value) -> Value {
+        json!({ "kind": "unbounded_vector", "element": element, "fixed_size": null })
+    }
+
+    pub(super) fn bitvector(length: usize) -> Value {
+        json!({ "kind": "bitvector", "length": length, "fixed_size": length.div_ceil(8) })
+    }
+
+    pub(super) fn bitlist(limit: usize) -> Value {
+        json!({ "kind": "bitlist", "limit": limit, "fixed_size": null })
+    }
+
+    pub(super) fn optional(element: Value) -> Value {
+        json!({ "kind": "optional", "element": element, "fixed_size": null })
+    }
+
+    pub(super) fn container(fields: Vec<Value>) -> Value {
+        let fixed_size = fields
+            .iter()
+            .map(|field| field["type"]["fixed_size"].as_u64())
+            .sum::<Option<u64>>();
+        json!({ "kind": "container", "fields": fields, "fixed_size": fixed_size })
+    }
+}
+
+macro_rules! impl_uint_schema {
+    ($type:ty, $bits:expr) => {
+        impl SszSchema for $type {
+            fn ssz_schema() -> String {
+                format!("uint{}", $bits)
+            }
+
+            #[cfg(feature = "reflect")]
+            fn ssz_schema_json() -> serde_json::Value {
+                schema_json::uint($bits)
+            }
+        }
+    };
+}
+
+impl_uint_schema!(u8, 8);
+impl_uint_schema!(u16, 16);
+impl_uint_schema!(u32, 32);
+impl_uint_schema!(u64, 64);
+impl_uint_schema!(u128, 128);
+impl_uint_schema!(U256, 256);
+
+impl SszSchema for bool {
+    fn ssz_schema() -> String {
+        "boolean".into()
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        schema_json::boolean()
+    }
+}
+
+impl<T: SszSchema, const N: usize> SszSchema for [T; N] {
+    fn ssz_schema() -> String {
+        format!("vector({},{N})", T::ssz_schema())
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        schema_json::vector(T::ssz_schema_json(), N)
+    }
+}
+
+impl<T: SszSchema, const N: usize> SszSchema for FixedVector<T, N> {
+    fn ssz_schema() -> String {
+        <[T; N]>::ssz_schema()
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        <[T; N]>::ssz_schema_json()
+    }
+}
+
+impl<const N: usize> SszSchema for FixedBytes<N> {
+    fn ssz_schema() -> String {
+        <[u8; N]>::ssz_schema()
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        <[u8; N]>::ssz_schema_json()
+    }
+}
+
+impl<T: SszSchema, const N: usize> SszSchema for List<T, N> {
+    fn ssz_schema() -> String {
+        format!("list({},{N})", T::ssz_schema())
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        schema_json::list(T::ssz_schema_json(), N)
+    }
+}
+
+/// A bare `Vec<T>` merkleizes as a vector of its runtime length, with no length mix-in, so it
+/// is described as a vector without a length rather than as a list.
+impl<T: SszSchema> SszSchema for Vec<T> {
+    fn ssz_schema() -> String {
+        format!("vector({})", T::ssz_schema())
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        schema_json::unbounded_vector(T::ssz_schema_json())
+    }
+}
+
+impl SszSchema for Bytes {
+    fn ssz_schema() -> String {
+        Vec::<u8>::ssz_schema()
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        Vec::<u8>::ssz_schema_json()
+    }
+}
+
+impl<const N: usize> SszSchema for BitList<N> {
+    fn ssz_schema() -> String {
+        format!("bitlist({N})")
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        schema_json::bitlist(N)
+    }
+}
+
+impl<const N: usize> SszSchema for BitVector<N> {
+    fn ssz_schema() -> String {
+        format!("bitvector({N})")
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        schema_json::bitvector(N)
+    }
+}
+
+impl<T: SszSchema> SszSchema for Option<T> {
+    fn ssz_schema() -> String {
+        format!("optional({})", T::ssz_schema())
+    }
+
+    #[cfg(feature = "reflect")]
+    fn ssz_schema_json() -> serde_json::Value {
+        schema_json::optional(T::ssz_schema_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Checkpoint;
+
+    impl SszSchema for Checkpoint {
+        fn ssz_schema() -> String {
+            container_schema(&[u64::ssz_schema(), <[u8; 32]>::ssz_schema()])
+        }
+
+        #[cfg(feature = "reflect")]
+        fn ssz_schema_json() -> serde_json::Value {
+            container_schema_json(&[
+                ("epoch", u64::ssz_schema_json()),
+                ("root", <[u8; 32]>::ssz_schema_json()),
+            ])
+        }
+    }
+
+    struct Attestation;
+
+    impl SszSchema for Attestation {
+        fn ssz_schema() -> String {
+            container_schema(&[u64::ssz_schema(), List::<u8, 256>::ssz_schema()])
+        }
+
+        #[cfg(feature = "reflect")]
+        fn ssz_schema_json() -> serde_json::Value {
+            container_schema_json(&[
+                ("slot", u64::ssz_schema_json()),
+                ("data", List::<u8, 256>::ssz_schema_json()),
+            ])
+        }
+    }
+
+    #[test]
+    fn test_ssz_schema() {
+        assert_eq!(u64::ssz_schema(), "uint64");
+        assert_eq!(List::<u8, 256>::ssz_schema(), "list(uint8,256)");
+        assert_eq!(
+            Attestation::ssz_schema(),
+            "container(uint64,list(uint8,256))"
+        );
+        assert_eq!(Option::<BitList<8>>::ssz_schema(), "optional(bitlist(8))");
+        assert_eq!(Vec::<u8>::ssz_schema(), "vector(uint8)");
+        assert_eq!(Bytes::ssz_schema(), "vector(uint8)");
+    }
+
+    #[test]
+    fn test_ssz_type_hash() {
+        // Structurally identical schemas hash the same, whatever the Rust type.
+        assert_eq!(
+            <[u8; 32]>::ssz_type_hash(),
+            FixedVector::<u8, 32>::ssz_type_hash()
+        );
+        assert_eq!(
+            <[u8; 32]>::ssz_type_hash(),
+            FixedBytes::<32>::ssz_type_hash()
+        );
+        assert_eq!(
+            Checkpoint::ssz_type_hash(),
+            B256::from_slice(&Sha256::digest(
+                container_schema(&["uint64".into(), "vector(uint8,32)".into()]).as_bytes()
+            ))
+        );
+
+        assert_ne!(
+            List::<u64, 4>::ssz_type_hash(),
+            List::<u64, 8>::ssz_type_hash()
+        );
+        assert_ne!(List::<u64, 4>::ssz_type_hash(), <[u64; 4]>::ssz_type_hash());
+        assert_ne!(Vec::<u64>::ssz_type_hash(), List::<u64, 4>::ssz_type_hash());
+        assert_ne!(Vec::<u64>::ssz_type_hash(), <[u64; 4]>::ssz_type_hash());
+        assert_ne!(Checkpoint::ssz_type_hash(), Attestation::ssz_type_hash());
+    }
+
+    #[cfg(feature = "reflect")]
+    #[test]
+    fn test_ssz_schema_json() {
+        use serde_json::json;
+
+        let schema = Attestation::ssz_schema_json();
+        assert_eq!(schema["kind"], "container");
+        assert_eq!(schema["fields"][0]["name"], "slot");
+        assert_eq!(schema["fields"][0]["type"]["kind"], "uint");
+        assert_eq!(schema["fields"][1]["name"], "data");
+        assert_eq!(schema["fields"][1]["type"]["kind"], "list");
+        assert_eq!(schema["fields"][1]["type"]["limit"], 256);
+        assert_eq!(schema["fixed_size"], json!(null));
+
+        assert_eq!(
+            Checkpoint::ssz_schema_json(),
+            json!({
+                "kind": "container",
+                "fields": [
+                    { "name": "epoch", "type": { "kind": "uint", "bits": 64, "fixed_size": 8 } },
+                    { "name": "root", "type": {
+                        "kind": "vector",
+                        "element": { "kind": "uint", "bits": 8, "fixed_size": 1 },
+                        "length": 32,
+                        "fixed_size": 32,
+                    } },
+                ],
+                "fixed_size": 40,
+            })
+        );
+        assert_eq!(
+            Option::<BitList<8>>::ssz_schema_json(),
+            json!({
+                "kind": "optional",
+                "element": { "kind": "bitlist", "limit": 8, "fixed_size": null },
+                "fixed_size": null,
+            })
+        );
+        assert_eq!(
+            Vec::<u8>::ssz_schema_json(),
+            json!({
+                "kind": "unbounded_vector",
+                "element": { "kind": "uint", "bits": 8, "fixed_size": 1 },
+                "fixed_size": null,
+            })
+        );
+    }
+}
@@ -1,26 +1,118 @@
 //! This module provides a simple serialization and deserialization mechanism for data structures.
 
-use crate::SSZError;
+use crate::merkleization::get_depth;
+use crate::{CONTEXT_BYTES_LEN, SSZError};
 use alloc::vec::Vec;
 use alloy_primitives::B256;
 
 /// The `SimpleSerialize` trait defines methods for serializing data structures
-pub trait SimpleSerialize: Sized {
+pub trait SimpleSerialize {
     /// Serializes the data structure into a byte vector.
     fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SSZError>;
+
+    /// Returns the number of bytes `serialize` writes for this value.
+    ///
+    /// The default serializes into a scratch buffer; fixed-size and collection types override
+    /// it to compute the length without allocating. Fails whenever `serialize` would, so offsets
+    /// computed from it are never based on a value that can't be encoded.
+    fn ssz_bytes_len(&self) -> Result<usize, SSZError> {
+        let mut buffer = Vec::new();
+        self.serialize(&mut buffer)?;
+        Ok(buffer.len())
+    }
+
+    /// Writes the same bytes as `serialize` into `writer`, returning how many were written.
+    ///
+    /// The default serializes into a scratch buffer and writes it in one go. Lists and
+    /// containers override it to stream each element straight through: offsets are computed
+    /// up front from `ssz_bytes_len`, so nothing needs to be buffered. Encoding errors are
+    /// reported as [`std::io::ErrorKind::Other`].
+    #[cfg(feature = "std")]
+    fn serialize_to_writer(&self, writer: &mut dyn std::io::Write) -> std::io::Result<usize> {
+        let mut buffer = Vec::new();
+        self.serialize(&mut buffer).map_err(std::io::Error::other)?;
+        writer.write_all(&buffer)?;
+        Ok(buffer.len())
+    }
 }
 
 /// The `SimpleDeserialize` trait defines methods for deserializing data structures
 pub trait SimpleDeserialize: Sized {
     /// Deserializes the data structure from a byte slice.
     fn deserialize(data: &[u8]) -> Result<Self, SSZError>;
+
+    /// Deserializes a fixed-size value from a slice the caller has already checked is exactly
+    /// `fixed_size()` bytes long.
+    ///
+    /// Collection decoders call this per element after validating the total length once, so
+    /// fixed-size containers can override it to skip their own length check.
+    fn deserialize_fixed(data: &[u8]) -> Result<Self, SSZError> {
+        Self::deserialize(data)
+    }
+}
+
+/// Deserializes `T` from `data`, rejecting inputs longer than `max_bytes` before decoding.
+///
+/// This bounds the size of a whole message, independently of any per-type capacity.
+pub fn deserialize_size_limited<T>(data: &[u8], max_bytes: usize) -> Result<T, SSZError>
+where
+    T: SimpleDeserialize,
+{
+    if data.len() > max_bytes {
+        return Err(SSZError::InputTooLarge {
+            limit: max_bytes,
+            got: data.len(),
+        });
+    }
+    T::deserialize(data)
+}
+
+/// Writes `context` (e.g. a fork digest) followed by the SSZ encoding of `value`.
+///
+/// Returns the number of bytes written, including the context.
+pub fn encode_with_context<T>(
+    value: &T,
+    context: &[u8; CONTEXT_BYTES_LEN],
+    out: &mut Vec<u8>,
+) -> Result<usize, SSZError>
+where
+    T: SimpleSerialize,
+{
+    out.extend_from_slice(context);
+    Ok(CONTEXT_BYTES_LEN + value.serialize(out)?)
+}
+
+/// Strips the context prefix written by [`encode_with_context`] and decodes the rest as `T`.
+pub fn decode_with_context<T>(data: &[u8]) -> Result<([u8; CONTEXT_BYTES_LEN], T), SSZError>
+where
+    T: SimpleDeserialize,
+{
+    let (context, rest) =
+        data.split_first_chunk::<CONTEXT_BYTES_LEN>()
+            .ok_or(SSZError::InvalidByteLength {
+                expected: CONTEXT_BYTES_LEN,
+                got: data.len(),
+            })?;
+    Ok((*context, T::deserialize(rest)?))
+}
+
+/// The `SimpleDeserializeRef` trait deserializes data structures that may borrow from the input.
+pub trait SimpleDeserializeRef<'a>: Sized {
+    /// Deserializes the data structure from a byte slice, borrowing from it where possible.
+    fn deserialize_ref(data: &'a [u8]) -> Result<Self, SSZError>;
 }
 
 /// The `SszTypeInfo` trait provides information about the size characteristics of a type.
 pub trait SszTypeInfo {
-    /// If Some(size), then type is fixed-size with known size in bytes.
-    /// If None, then it's variable-size (e.g. Vec<u8>, String, etc).
-    fn is_fixed_size() -> bool;
+    /// Whether the type is fixed-size, usable in const context.
+    ///
+    /// Container code branches on this at compile time instead of calling `is_fixed_size`.
+    const IS_FIXED: bool;
+
+    /// Returns true if the type is fixed-size; variable-size types (e.g. `Vec<u8>`) return false.
+    fn is_fixed_size() -> bool {
+        Self::IS_FIXED
+    }
 
     /// If fixed-size, returns the size in bytes.
     fn fixed_size() -> Option<usize>;
@@ -41,6 +133,19 @@ pub trait SszTypeInfo {
     }
 }
 
+/// Extra layer a type mixes into its root on top of its chunk tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MixInKind {
+    /// The root is the chunk tree root.
+    None,
+    /// The length is mixed in (lists, bitlists).
+    Length,
+    /// The selector is mixed in (unions, optionals).
+    Selector,
+    /// An auxiliary root is mixed in (stable container active fields).
+    Aux,
+}
+
 /// Merkleization trait for SSZ types
 pub trait Merkleize {
     /// Calculate the hash tree root of this value
@@ -53,4 +158,140 @@ pub trait Merkleize {
     {
         1 // Default for basic types
     }
+
+    /// Number of sibling hashes in a proof from a chunk of this type up to its root.
+    ///
+    /// Types that mix a length or auxiliary value into their root add one extra layer.
+    fn merkle_depth() -> u32
+    where
+        Self: Sized,
+    {
+        get_depth(Self::chunk_count())
+    }
+
+    /// Reports which extra layer, if any, this type mixes into its root.
+    fn mix_in_kind() -> MixInKind
+    where
+        Self: Sized,
+    {
+        MixInKind::None
+    }
+}
+
+/// Asserts that `T::fixed_size()` matches the number of bytes a default `T` serializes to.
+///
+/// Catches hand-written `SszTypeInfo` constants that drifted from the serializer.
+#[cfg(test)]
+pub(crate) fn assert_fixed_size_correct<T>()
+where
+    T: SimpleSerialize + SszTypeInfo + Default,
+{
+    let mut buffer = Vec::new();
+    T::default()
+        .serialize(&mut buffer)
+        .expect("can serialize default value");
+    assert_eq!(
+        Some(buffer.len()),
+        T::fixed_size(),
+        "fixed_size() does not match the serialized length of {}",
+        core::any::type_name::<T>()
+    );
+}
+
+/// Live-instance counters for [`Tracked`], one per slot so concurrently running tests don't
+/// share a count.
+#[cfg(test)]
+static TRACKED_LIVE: [core::sync::atomic::AtomicUsize; 2] =
+    [const { core::sync::atomic::AtomicUsize::new(0) }; 2];
+
+/// A unit value that decodes from a 4-byte `u32`, rejecting `u32::MAX` with `InvalidByte`,
+/// and counts its live instances in slot `SLOT`.
+///
+/// Lets tests check that a decoder which fails part way drops each element it already
+/// decoded exactly once.
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct Tracked<const SLOT: usize>;
+
+#[cfg(test)]
+impl<const SLOT: usize> Tracked<SLOT> {
+    /// Returns how many `Tracked<SLOT>` values are currently alive.
+    pub(crate) fn live() -> usize {
+        TRACKED_LIVE[SLOT].load(core::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+impl<const SLOT: usize> Drop for Tracked<SLOT> {
+    fn drop(&mut self) {
+        TRACKED_LIVE[SLOT].fetch_sub(1, core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+impl<const SLOT: usize> SszTypeInfo for Tracked<SLOT> {
+    const IS_FIXED: bool = true;
+
+    fn fixed_size() -> Option<usize> {
+        Some(4)
+    }
+}
+
+#[cfg(test)]
+impl<const SLOT: usize> SimpleDeserialize for Tracked<SLOT> {
+    fn deserialize(data: &[u8]) -> Result<Self, SSZError> {
+        if u32::deserialize(data)? == u32::MAX {
+            return Err(SSZError::InvalidByte);
+        }
+        TRACKED_LIVE[SLOT].fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+        Ok(Tracked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_deserialize_size_limited() {
+        let data = vec![0u8; 1024];
+        assert_eq!(
+            deserialize_size_limited::<Vec<u8>>(&data, 512),
+            Err(SSZError::InputTooLarge {
+                limit: 512,
+                got: 1024
+            })
+        );
+
+        assert_eq!(
+            deserialize_size_limited::<Vec<u8>>(&data[..512], 512),
+            Ok(vec![0u8; 512])
+        );
+    }
+
+    #[test]
+    fn test_encode_with_context_roundtrip() {
+        use crate::ssz_list::List;
+
+        let fork_digest = [0x6a, 0x95, 0xa1, 0xa9];
+        let list = List::<u64, 8>::new(vec![1u64, 2, 3]).unwrap();
+
+        let mut out = vec![];
+        let written = encode_with_context(&list, &fork_digest, &mut out).unwrap();
+        assert_eq!(written, 4 + 24);
+        assert_eq!(out[..4], fork_digest);
+
+        let (context, decoded) = decode_with_context::<List<u64, 8>>(&out).unwrap();
+        assert_eq!(context, fork_digest);
+        assert_eq!(decoded, list);
+
+        assert_eq!(
+            decode_with_context::<List<u64, 8>>(&out[..3]),
+            Err(SSZError::InvalidByteLength {
+                expected: 4,
+                got: 3
+            })
+        );
+    }
 }